@@ -6,6 +6,7 @@ use crate::path_ext::PathExt;
 use crate::terminators_entry::{conjectr_shell, Shell, Terms};
 use clap::ValueEnum;
 use colored::Colorize;
+use log::debug;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use thiserror::Error;
@@ -18,16 +19,17 @@ pub struct AppVan {
     json: bool,
     #[clap(long, hide = true)]
     plural: bool,
-    /// Trigger NodeJS version change on entering a node project folder
+    /// Trigger `NodeJS` version change on entering a node project folder
     #[clap(long)]
     on_enter: bool,
 }
 
 fn create_symlink_path() -> String {
     format!(
-        "{}_{}",
-        std::process::id(),
+        "{}_{}_{}",
+        crate::shims::owner_pid(),
         chrono::Utc::now().timestamp_millis(),
+        crate::shims::SHELL_MARKER,
     )
 }
 
@@ -60,6 +62,9 @@ impl Command for AppVan {
         }
 
         let plural_ctx = create_symlink(config)?;
+        if let Err(err) = crate::shims::remove_stale(config) {
+            debug!("Unable to remove the stale shims: {}", err);
+        }
         let base_dir = config.base_dir_with_default();
 
         let env_vars = [
@@ -0,0 +1,32 @@
+use super::command::Command;
+use crate::app_config::AppConfig;
+use crate::outln;
+use colored::Colorize;
+use thiserror::Error;
+
+#[derive(clap::Parser, Debug)]
+pub struct CleanShims {}
+
+impl Command for CleanShims {
+    type Error = Error;
+
+    fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        let removed = crate::shims::remove_stale(config)
+            .map_err(|source| Error::CantListShims { source })?;
+
+        outln!(
+            config,
+            Info,
+            "Removed {} stale shim(s) from {}",
+            removed.len().to_string().cyan(),
+            config.plural_ctx_storage().display()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Unable to list the shims: {}", source)]
+    CantListShims { source: std::io::Error },
+}
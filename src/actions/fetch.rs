@@ -0,0 +1,181 @@
+use super::command::Command;
+use super::firstrun::{resolve_version, Error as ResolveError};
+use crate::app_config::AppConfig;
+use crate::cpu_arch::CPUArch;
+use crate::fetcher::{
+    archives_for, download_from, fetch_archive, filename_for_version, Error as DownloaderError,
+};
+use crate::loaders::{ProgressConfig, ResponseProgress};
+use crate::ni_remote;
+use crate::outln;
+use crate::prtl_folder::DirectoryPortal;
+use crate::system_info::Platform;
+use crate::user_version::UserVersion;
+use crate::version::Version;
+use colored::Colorize;
+use indicatif::ProgressDrawTarget;
+use std::io::Write;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(clap::Parser, Debug)]
+pub struct Fetch {
+    /// The version to download, like `20`, `lts/iron` or `rc/23`
+    version: UserVersion,
+
+    /// The platform to download Node for. Defaults to this machine's.
+    /// The architecture is picked with the global `--cpu-arch`
+    #[clap(long, value_enum)]
+    platform: Option<Platform>,
+
+    /// The directory to download into
+    #[clap(long, value_name = "DIR")]
+    output: PathBuf,
+
+    /// Keep the downloaded archive instead of extracting it
+    #[clap(long)]
+    keep_archive: bool,
+
+    /// Show an interactive loaders bar for the download
+    #[clap(long, default_value_t)]
+    #[arg(value_enum)]
+    loaders: ProgressConfig,
+}
+
+impl Command for Fetch {
+    type Error = Error;
+
+    fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        let show_loaders = self.loaders.enabled(config);
+        let platform = self.platform.unwrap_or_else(Platform::current);
+        let cpu_arch = match (platform, config.cpu_arch) {
+            // Only Linux has musl builds, a musl machine fetching for another platform wants x64
+            (Platform::Darwin | Platform::Win, CPUArch::X64Musl) => CPUArch::X64,
+            (_, cpu_arch) => cpu_arch,
+        };
+
+        if let UserVersion::Full(version @ (Version::Bypassed | Version::Alias(_))) = self.version {
+            return Err(Error::UnfetchableVersion { version });
+        }
+
+        let dist_mirrors = self.version.channel().dist_mirrors(cpu_arch, config);
+        let remote_versions = ni_remote::list(&dist_mirrors, config)
+            .map_err(|source| Error::CantListRemoteVersions { source })?;
+        let version = resolve_version(&self.version, &remote_versions, config)
+            .map_err(|source| Error::CantResolveVersion { source })?;
+        let published_files = remote_versions
+            .iter()
+            .find(|x| x.version == version)
+            .map_or(&[][..], |x| x.files.as_slice());
+        let archives = archives_for(&version, published_files, platform, cpu_arch)?;
+
+        let extracted_path = self
+            .output
+            .join(format!("node-{version}-{platform}-{cpu_arch}"));
+        if !self.keep_archive && extracted_path.exists() {
+            return Err(Error::AlreadyFetched {
+                path: extracted_path,
+            });
+        }
+
+        std::fs::create_dir_all(&self.output).map_err(|source| Error::CantWriteOutput {
+            path: self.output.clone(),
+            source,
+        })?;
+        outln!(
+            config,
+            Info,
+            "Fetching {} for {}",
+            format!("Node {version}").cyan(),
+            format!("{platform}-{cpu_arch}").cyan()
+        );
+
+        let (path, _) = crate::mirrors::try_in_order(
+            &dist_mirrors,
+            config,
+            DownloaderError::is_outage,
+            |mirror| {
+                if self.keep_archive {
+                    let (response, archive) =
+                        fetch_archive(mirror, &version, &archives, platform, cpu_arch)?;
+                    let file_name = filename_for_version(
+                        &version,
+                        platform,
+                        cpu_arch,
+                        archive.file_extension(),
+                    );
+                    let path = self.output.join(file_name);
+                    save(response, &path, show_loaders)?;
+                    Ok(path)
+                } else {
+                    let portal = DirectoryPortal::new_in(&self.output, &extracted_path);
+                    download_from(
+                        mirror,
+                        &version,
+                        &archives,
+                        &portal,
+                        platform,
+                        cpu_arch,
+                        show_loaders,
+                    )?;
+                    std::fs::rename(portal.join("installation"), &extracted_path)?;
+                    Ok(extracted_path.clone())
+                }
+            },
+        )
+        .map_err(|source| Error::CantDownload { source })?;
+
+        outln!(config, Info, "Saved into {}", path.display());
+        Ok(())
+    }
+}
+
+/// Writes the archive into `path` only once it was downloaded completely
+fn save(
+    response: crate::http::Response,
+    path: &std::path::Path,
+    show_loaders: bool,
+) -> Result<(), DownloaderError> {
+    let mut partial_path = path.as_os_str().to_owned();
+    partial_path.push(".download");
+    let partial_path = PathBuf::from(partial_path);
+
+    let mut file = std::fs::File::create(&partial_path)?;
+    let copied = if show_loaders {
+        let mut progress = ResponseProgress::new(response, ProgressDrawTarget::stderr());
+        std::io::copy(&mut progress, &mut file)
+    } else {
+        let mut response = response;
+        std::io::copy(&mut response, &mut file)
+    };
+    if let Err(err) = copied.and_then(|_| file.flush()) {
+        std::fs::remove_file(&partial_path).ok();
+        return Err(err.into());
+    }
+    std::fs::rename(&partial_path, path)?;
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("The requested version can't be fetched: {}", version.v_str())]
+    UnfetchableVersion { version: Version },
+    #[error("Can't get remote versions: {}", source)]
+    CantListRemoteVersions { source: crate::http::Error },
+    #[error(transparent)]
+    CantResolveVersion { source: ResolveError },
+    #[error(transparent)]
+    NotPublished {
+        #[from]
+        source: DownloaderError,
+    },
+    #[error("{} already exists", path.display())]
+    AlreadyFetched { path: PathBuf },
+    #[error("Unable to write into {}: {}", path.display(), source)]
+    CantWriteOutput {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Unable to download the requested archive: {}", source)]
+    CantDownload { source: DownloaderError },
+}
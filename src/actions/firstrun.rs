@@ -1,12 +1,15 @@
 use super::command::Command;
-use crate::alias::create_alias;
+use crate::alias::{create_alias, create_default_alias_if_missing};
 use crate::app_config::AppConfig;
-use crate::cpu_arch::get_safe_cpu_arch;
-use crate::fetcher::{install_node_dist, Error as DownloaderError};
+use crate::cpu_arch::{get_safe_cpu_arch, CPUArch};
+use crate::fetcher::{self, install_node_dist, Error as DownloaderError};
+use crate::install_metadata::InstallMetadata;
 use crate::loaders::ProgressConfig;
 use crate::long_term_usage::LongTermType;
 use crate::ni_remote;
 use crate::outln;
+use crate::release_schedule::Schedule;
+use crate::source_build::{build_from_source, Error as SourceBuildError, SourceBuildOptions};
 use crate::user_version::UserVersion;
 use crate::version::Version;
 use crate::version_files::get_user_version_for_directory;
@@ -16,7 +19,8 @@ use thiserror::Error;
 
 #[derive(clap::Parser, Debug, Default)]
 pub struct FirstRun {
-    /// A version string. Can be a partial semver or a LTS version name by the format lts/NAME
+    /// A version string. Can be a partial semver, a LTS version name by the format lts/NAME,
+    /// or a version in a release channel by the format CHANNEL/VERSION, like rc/23 or nightly/latest
     pub version: Option<UserVersion>,
 
     /// Install latest LTS
@@ -32,6 +36,13 @@ pub struct FirstRun {
     #[clap(long, default_value_t)]
     #[arg(value_enum)]
     pub loaders: ProgressConfig,
+
+    /// Also install the headers, so native addons build without downloading them
+    #[clap(long)]
+    pub with_headers: bool,
+
+    #[clap(flatten)]
+    pub source_build: SourceBuildOptions,
 }
 
 impl FirstRun {
@@ -66,67 +77,47 @@ impl Command for FirstRun {
     fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
         let current_dir = std::env::current_dir().unwrap();
         let show_loaders = self.loaders.enabled(config);
+        let source_build = self.source_build.clone();
+        let with_headers = self.with_headers;
 
         let version_now = self
             .version()?
             .or_else(|| get_user_version_for_directory(current_dir, config))
             .ok_or(Error::CantConjectVersion)?;
 
-        let version = match version_now.clone() {
-            UserVersion::Full(Version::Semver(actual_version)) => Version::Semver(actual_version),
-            UserVersion::Full(v @ (Version::Bypassed | Version::Alias(_))) => {
-                return Err(Error::UninstallableVersion { version: v });
-            }
-            UserVersion::Full(Version::Lts(lts_type)) => {
-                let available_versions: Vec<_> = ni_remote::list(&config.node_dist_mirror)
-                    .map_err(|source| Error::CantListRemoteVersions { source })?;
-                let picked_version = lts_type
-                    .pick_latest(&available_versions)
-                    .ok_or_else(|| Error::CantFindRelevantLts {
-                        lts_type: lts_type.clone(),
-                    })?
-                    .version
-                    .clone();
-                debug!(
-                    "Resolved {} into Node version {}",
-                    Version::Lts(lts_type).v_str().cyan(),
-                    picked_version.v_str().cyan()
-                );
-                picked_version
-            }
-            UserVersion::Full(Version::Latest) => {
-                let available_versions: Vec<_> = ni_remote::list(&config.node_dist_mirror)
-                    .map_err(|source| Error::CantListRemoteVersions { source })?;
-                let picked_version = available_versions
-                    .last()
-                    .ok_or(Error::CantFindLatest)?
-                    .version
-                    .clone();
-                debug!(
-                    "Resolved {} into Node version {}",
-                    Version::Latest.v_str().cyan(),
-                    picked_version.v_str().cyan()
-                );
-                picked_version
-            }
-            version_now => {
-                let available_versions: Vec<_> = ni_remote::list(&config.node_dist_mirror)
-                    .map_err(|source| Error::CantListRemoteVersions { source })?
-                    .drain(..)
-                    .map(|x| x.version)
-                    .collect();
-
-                version_now
-                    .to_version(&available_versions, config)
-                    .ok_or(Error::CantFindNodeVersion {
-                        requested_version: version_now,
-                    })?
-                    .clone()
-            }
-        };
+        if let UserVersion::Full(v @ (Version::Bypassed | Version::Alias(_))) = version_now {
+            return Err(Error::UninstallableVersion { version: v });
+        }
+
+        // A build from source is native to this machine
+        if source_build.from_source && config.cpu_arch != CPUArch::default() {
+            return Err(Error::CantBuildForCPUArch {
+                cpu_arch: config.cpu_arch,
+            });
+        }
+
+        let dist_mirrors = version_now.channel().dist_mirrors(config.cpu_arch, config);
+        let remote_versions = ni_remote::list(&dist_mirrors, config)
+            .map_err(|source| Error::CantListRemoteVersions { source })?;
+
+        let version = resolve_version(&version_now, &remote_versions, config)?;
+
+        let published_files = remote_versions
+            .iter()
+            .find(|x| x.version == version)
+            .map_or(&[][..], |x| x.files.as_slice());
+
+        Schedule::load(config).warn_if_eol(&version, config);
 
         // Automatically swap Apple Silicon to x64 cpu_arch for appropriate versions.
-        let safe_cpu_arch = get_safe_cpu_arch(config.cpu_arch, &version);
+        let safe_cpu_arch = if source_build.from_source {
+            CPUArch::default()
+        } else {
+            get_safe_cpu_arch(config.cpu_arch, &version)
+        };
+
+        // Held until the version is installed and tagged, so a concurrent install waits for it
+        let _lock = crate::file_lock::lock_version(config, &version)?;
 
         let version_str = format!("Node {}", &version);
         outln!(
@@ -137,23 +128,32 @@ impl Command for FirstRun {
             safe_cpu_arch.as_str()
         );
 
-        match install_node_dist(
+        let mirror = install_version(
             &version,
-            &config.node_dist_mirror,
-            config.installations_dir(),
+            published_files,
+            &dist_mirrors,
+            &source_build,
             safe_cpu_arch,
             show_loaders,
-        ) {
-            Err(err @ DownloaderError::VersionAlreadyInstalled { .. }) => {
-                outln!(config, Error, "{} {}", "warning:".bold().yellow(), err);
-            }
-            Err(source) => Err(Error::DownloadError { source })?,
-            Ok(()) => {}
-        };
+            config,
+        )?;
 
-        if !config.version_std_dir().exists() {
-            debug!("Tagging {} as the default version", version.v_str().cyan());
-            create_alias(config, "default", &version)?;
+        if with_headers {
+            install_headers(
+                &version,
+                safe_cpu_arch,
+                mirror,
+                &dist_mirrors,
+                show_loaders,
+                config,
+            )?;
+        }
+        let installation =
+            crate::arch_installs::arch_dir(config, &version, safe_cpu_arch).join("installation");
+        crate::node_headers::expose(&installation)?;
+
+        if create_default_alias_if_missing(config, &version)? {
+            debug!("Tagged {} as the default version", version.v_str().cyan());
         }
 
         if let Some(tagged_alias) = version_now.conjectrred_alias() {
@@ -162,13 +162,157 @@ impl Command for FirstRun {
 
         if config.corepack_enabled() {
             outln!(config, Info, "Enabling corepack for {}", version_str.cyan());
-            enable_corepack(&version, config)?;
+            enable_corepack(&version, safe_cpu_arch, config)?;
         }
 
         Ok(())
     }
 }
 
+/// Resolves the version to install from the versions the mirror has.
+pub(crate) fn resolve_version(
+    version_now: &UserVersion,
+    remote_versions: &[ni_remote::IndexedNodeVersion],
+    config: &AppConfig,
+) -> Result<Version, Error> {
+    let version = match version_now.clone() {
+        UserVersion::Full(Version::Semver(actual_version)) => Version::Semver(actual_version),
+        UserVersion::Full(Version::Lts(lts_type)) => {
+            let picked_version = lts_type
+                .pick_latest(remote_versions)
+                .ok_or_else(|| Error::CantFindRelevantLts {
+                    lts_type: lts_type.clone(),
+                })?
+                .version
+                .clone();
+            debug!(
+                "Resolved {} into Node version {}",
+                Version::Lts(lts_type).v_str().cyan(),
+                picked_version.v_str().cyan()
+            );
+            picked_version
+        }
+        UserVersion::Full(Version::Latest) => {
+            let picked_version = remote_versions
+                .last()
+                .ok_or(Error::CantFindLatest)?
+                .version
+                .clone();
+            debug!(
+                "Resolved {} into Node version {}",
+                Version::Latest.v_str().cyan(),
+                picked_version.v_str().cyan()
+            );
+            picked_version
+        }
+        version_now => {
+            let available_versions: Vec<_> =
+                remote_versions.iter().map(|x| &x.version).collect();
+
+            version_now
+                .to_version(available_versions, config)
+                .ok_or(Error::CantFindNodeVersion {
+                    requested_version: version_now,
+                })?
+                .clone()
+        }
+    };
+    Ok(version)
+}
+
+/// Downloads the version, or builds it from source, into the installations directory.
+/// A version that is already installed is only a warning.
+/// Returns the mirror it was installed from, if it was installed now.
+fn install_version(
+    version: &Version,
+    published_files: &[String],
+    dist_mirrors: &[url::Url],
+    source_build: &SourceBuildOptions,
+    cpu_arch: CPUArch,
+    show_loaders: bool,
+    config: &AppConfig,
+) -> Result<Option<url::Url>, Error> {
+    if source_build.from_source {
+        let dist_mirror = crate::mirrors::by_health(dist_mirrors, config)[0];
+        match build_from_source(
+            version,
+            dist_mirror,
+            source_build,
+            cpu_arch,
+            config,
+            show_loaders,
+        ) {
+            Err(err @ SourceBuildError::VersionAlreadyInstalled { .. }) => {
+                outln!(config, Error, "{} {}", "warning:".bold().yellow(), err);
+                Ok(None)
+            }
+            Err(source) => Err(Error::SourceBuildError { source }),
+            Ok(()) => Ok(Some(dist_mirror.clone())),
+        }
+    } else {
+        match install_node_dist(
+            version,
+            published_files,
+            dist_mirrors,
+            config.installations_dir(),
+            cpu_arch,
+            show_loaders,
+            config,
+        ) {
+            Err(err @ DownloaderError::VersionAlreadyInstalled { .. }) => {
+                outln!(config, Error, "{} {}", "warning:".bold().yellow(), err);
+                Ok(None)
+            }
+            Err(source) => Err(Error::DownloadError { source }),
+            Ok(mirror) => {
+                if mirror != dist_mirrors[0] {
+                    outln!(
+                        config,
+                        Info,
+                        "Downloaded from the fallback mirror {}",
+                        crate::http::redact(&mirror)
+                    );
+                }
+                Ok(Some(mirror))
+            }
+        }
+    }
+}
+
+/// Installs the headers of a version from the mirror it was installed from,
+/// or from any of the mirrors when it was installed before.
+fn install_headers(
+    version: &Version,
+    cpu_arch: CPUArch,
+    mirror: Option<url::Url>,
+    dist_mirrors: &[url::Url],
+    show_loaders: bool,
+    config: &AppConfig,
+) -> Result<(), Error> {
+    let version_dir = crate::arch_installs::arch_dir(config, version, cpu_arch);
+    let mut metadata = InstallMetadata::read(&version_dir);
+    if metadata.headers {
+        debug!("The headers of {} are already installed", version.v_str());
+        return Ok(());
+    }
+
+    outln!(
+        config,
+        Info,
+        "Installing the headers of {}",
+        format!("Node {version}").cyan()
+    );
+    let mirrors = mirror.map_or_else(|| dist_mirrors.to_vec(), |mirror| vec![mirror]);
+    crate::mirrors::try_in_order(&mirrors, config, DownloaderError::is_outage, |mirror| {
+        fetcher::install_headers(version, mirror, &version_dir, cpu_arch, show_loaders)
+    })
+    .map_err(|source| Error::CantInstallHeaders { source })?;
+
+    metadata.headers = true;
+    metadata.write(&version_dir)?;
+    Ok(())
+}
+
 fn tag_alias(config: &AppConfig, matched_version: &Version, alias: &Version) -> Result<(), Error> {
     let alias_name = alias.v_str();
     debug!(
@@ -181,8 +325,9 @@ fn tag_alias(config: &AppConfig, matched_version: &Version, alias: &Version) ->
     Ok(())
 }
 
-fn enable_corepack(version: &Version, config: &AppConfig) -> Result<(), Error> {
-    let corepack_path = version.installation_path(config);
+fn enable_corepack(version: &Version, cpu_arch: CPUArch, config: &AppConfig) -> Result<(), Error> {
+    // The `installation` link of the version leads to the architecture installed first
+    let corepack_path = crate::arch_installs::arch_dir(config, version, cpu_arch).join("installation");
     let corepack_path = if cfg!(windows) {
         corepack_path.join("corepack.cmd")
     } else {
@@ -198,6 +343,10 @@ fn enable_corepack(version: &Version, config: &AppConfig) -> Result<(), Error> {
 pub enum Error {
     #[error("Unable to download the requested binary: {}", source)]
     DownloadError { source: DownloaderError },
+    #[error("Unable to install the headers: {}", source)]
+    CantInstallHeaders { source: DownloaderError },
+    #[error("Unable to build from source: {}", source)]
+    SourceBuildError { source: SourceBuildError },
     #[error(transparent)]
     IoError {
         #[from]
@@ -225,4 +374,9 @@ pub enum Error {
     UninstallableVersion { version: Version },
     #[error("Too many versions provided. Please don't use --lts with a version string.")]
     TooManyVersionsProvided,
+    #[error(
+        "Unable to build from source for {}, builds from source are only for this machine's architecture",
+        cpu_arch
+    )]
+    CantBuildForCPUArch { cpu_arch: CPUArch },
 }
@@ -0,0 +1,194 @@
+use super::command::Command;
+use crate::alias::create_default_alias_if_missing;
+use crate::app_config::AppConfig;
+use crate::checksums::{sha256_file, Shasums};
+use crate::fetcher::{extract_installation, Error as ExtractError};
+use crate::outln;
+use crate::pressed::Archive;
+use crate::prtl_folder::DirectoryPortal;
+use crate::system_info::Platform;
+use crate::version::Version;
+use colored::Colorize;
+use log::debug;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(clap::Parser, Debug)]
+pub struct Import {
+    /// A Node archive, like node-v20.11.1-linux-x64.tar.xz
+    archive: PathBuf,
+
+    /// Verify the archive against a SHASUMS256.txt file
+    #[clap(long, value_name = "FILE")]
+    shasums: Option<PathBuf>,
+
+    /// The version of the archive, when its file name doesn't tell
+    #[clap(long = "as-version", value_name = "VERSION")]
+    version: Option<Version>,
+}
+
+/// Splits an archive name like `node-v20.11.1-linux-x64.tar.xz`
+/// into its version and platform, like `v20.11.1` and `linux-x64`.
+fn parse_file_name<'a>(file_name: &'a str, archive: &Archive) -> Option<(&'a str, &'a str)> {
+    const PLATFORMS: [&str; 5] = ["linux", "darwin", "win", "aix", "sunos"];
+
+    let stem = file_name
+        .strip_suffix(archive.file_extension())?
+        .strip_suffix('.')?
+        .strip_prefix("node-")?;
+    // Both the version and the architecture can contain dashes,
+    // like `v23.0.0-rc.1` and `x64-musl`, so look for the platform
+    let index = PLATFORMS
+        .iter()
+        .find_map(|platform| stem.find(&format!("-{platform}-")))?;
+    Some((&stem[..index], &stem[index + 1..]))
+}
+
+impl Command for Import {
+    type Error = Error;
+
+    fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        let file_name = self
+            .archive
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| Error::NotAnArchive {
+                path: self.archive.clone(),
+            })?;
+        let archive = Archive::for_file_name(file_name).ok_or_else(|| Error::NotAnArchive {
+            path: self.archive.clone(),
+        })?;
+
+        let parsed = parse_file_name(file_name, archive);
+        let version = match (self.version, parsed) {
+            (Some(version), _) => version,
+            (None, Some((version, _))) => {
+                Version::parse(version).map_err(|_| Error::CantDetectVersion {
+                    file_name: file_name.to_string(),
+                })?
+            }
+            (None, None) => Err(Error::CantDetectVersion {
+                file_name: file_name.to_string(),
+            })?,
+        };
+        if !matches!(version, Version::Semver(_)) {
+            return Err(Error::UninstallableVersion { version });
+        }
+
+        // Named like in the archive names, which use `darwin` and `win`
+        let expected_platform = format!("{}-{}", Platform::current(), config.cpu_arch);
+        if let Some((_, platform)) = parsed.filter(|(_, platform)| *platform != expected_platform) {
+            outln!(
+                config,
+                Error,
+                "{} {} is built for {}, but this machine runs {}",
+                "warning:".yellow().bold(),
+                file_name,
+                platform,
+                expected_platform
+            );
+        }
+
+        if let Some(shasums_path) = &self.shasums {
+            verify(&self.archive, file_name, shasums_path)?;
+            debug!("The checksum of {} matches", file_name);
+        }
+
+        let cpu_arch = parsed
+            .and_then(|(_, platform)| platform.split_once('-')?.1.parse().ok())
+            .unwrap_or(config.cpu_arch);
+        let _lock = crate::file_lock::lock_version(config, &version)?;
+        crate::arch_installs::migrate(config, &version)?;
+        let installations_dir = config.installations_dir();
+        let installation_dir = crate::arch_installs::arch_dir(config, &version, cpu_arch);
+        if installation_dir.exists() {
+            return Err(Error::VersionAlreadyInstalled {
+                path: installation_dir,
+            });
+        }
+
+        outln!(
+            config,
+            Info,
+            "Importing {} as {}",
+            file_name,
+            version.v_str().cyan()
+        );
+        let temp_installations_dir = installations_dir.join(".downloads");
+        std::fs::create_dir_all(&temp_installations_dir)?;
+        let portal = DirectoryPortal::new_in(&temp_installations_dir, installation_dir);
+        let reader = std::fs::File::open(&self.archive)?;
+        extract_installation(archive, reader, &portal)
+            .map_err(|source| Error::CantExtract { source })?;
+        std::fs::create_dir_all(installations_dir.join(version.v_str()))?;
+        let installation_dir = portal.teleport()?;
+        crate::node_headers::expose(&installation_dir.join("installation"))?;
+        crate::arch_installs::set_primary_if_missing(config, &version, cpu_arch)?;
+
+        if create_default_alias_if_missing(config, &version)? {
+            debug!("Tagged {} as the default version", version.v_str().cyan());
+        }
+
+        Ok(())
+    }
+}
+
+fn verify(archive: &Path, file_name: &str, shasums_path: &Path) -> Result<(), Error> {
+    let shasums = Shasums::read(shasums_path).map_err(|source| Error::CantReadShasums {
+        path: shasums_path.to_path_buf(),
+        source,
+    })?;
+    let expected = shasums.get(file_name).ok_or_else(|| Error::NotInShasums {
+        file_name: file_name.to_string(),
+        path: shasums_path.to_path_buf(),
+    })?;
+    let actual = sha256_file(archive)?;
+    if actual != expected {
+        return Err(Error::ChecksumMismatch {
+            file_name: file_name.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IoError {
+        #[from]
+        source: std::io::Error,
+    },
+    #[error("{} is not a Node archive this platform can extract", path.display())]
+    NotAnArchive { path: PathBuf },
+    #[error(
+        "Unable to detect the version of {}. Please provide it with --as-version",
+        file_name
+    )]
+    CantDetectVersion { file_name: String },
+    #[error("The requested version is not installable: {}", version.v_str())]
+    UninstallableVersion { version: Version },
+    #[error("Unable to read {}: {}", path.display(), source)]
+    CantReadShasums {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{} is not listed in {}", file_name, path.display())]
+    NotInShasums { file_name: String, path: PathBuf },
+    #[error(
+        "The checksum of {} doesn't match: expected {}, got {}",
+        file_name,
+        expected,
+        actual
+    )]
+    ChecksumMismatch {
+        file_name: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Version already installed at {:?}", path)]
+    VersionAlreadyInstalled { path: PathBuf },
+    #[error("Unable to extract the archive: {}", source)]
+    CantExtract { source: ExtractError },
+}
@@ -1,15 +1,32 @@
 use super::command::Command;
 use crate::app_config::AppConfig;
-use crate::version_now::{version_now, Error};
+use crate::version_now::{version_now, Error as VersionNowError};
+use crate::version_record::LocalState;
+use thiserror::Error;
 
 #[derive(clap::Parser, Debug)]
-pub struct InUse {}
+pub struct InUse {
+    /// Print the version as a JSON object, or `null` when none is in use.
+    /// See the README for the schema.
+    #[clap(long)]
+    json: bool,
+}
 
 impl Command for InUse {
     type Error = Error;
 
     fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
-        let version_string = match version_now(config)? {
+        let version = version_now(config).map_err(|source| Error::VersionNowError { source })?;
+
+        if self.json {
+            let local_state =
+                LocalState::load(config).map_err(|source| Error::CantReadAliases { source })?;
+            let record = version.map(|ver| local_state.record(&ver, None, true, config));
+            println!("{}", serde_json::to_string_pretty(&record).unwrap());
+            return Ok(());
+        }
+
+        let version_string = match version {
             Some(ver) => ver.v_str(),
             None => "none".into(),
         };
@@ -17,3 +34,11 @@ impl Command for InUse {
         Ok(())
     }
 }
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    VersionNowError { source: VersionNowError },
+    #[error("Unable to read aliases: {}", source)]
+    CantReadAliases { source: std::io::Error },
+}
@@ -0,0 +1,92 @@
+use super::command::Command;
+use crate::app_config::AppConfig;
+use crate::cpu_arch::CPUArch;
+use crate::outln;
+use crate::user_version::UserVersion;
+use crate::version::Version;
+use colored::Colorize;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Aliases enm creates or resolves by itself, which a linked version would clash with
+const RESERVED_NAMES: [&str; 4] = ["default", "latest", "lts", "system"];
+
+#[derive(clap::Parser, Debug)]
+pub struct Link {
+    /// The name to use the version with, like `custom-asan`
+    name: String,
+
+    /// The prefix Node is installed in, containing `bin/node`
+    prefix: PathBuf,
+}
+
+impl Command for Link {
+    type Error = Error;
+
+    fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        // Names which read as versions or aliases would be shadowed when resolving
+        let Ok(UserVersion::Full(Version::Alias(name))) = self.name.parse::<UserVersion>() else {
+            return Err(Error::InvalidName { name: self.name });
+        };
+        // Architectures name the directories in a version, and the name is a directory itself
+        if RESERVED_NAMES.contains(&name.as_str())
+            || name.parse::<CPUArch>().is_ok()
+            || name.contains(['/', '\\'])
+        {
+            return Err(Error::InvalidName { name });
+        }
+        let _lock = crate::file_lock::lock_version(config, &Version::Linked(name.clone()))?;
+        if config.aliases_dir().join(&name).exists() {
+            return Err(Error::AliasExists { name });
+        }
+        if config.installations_dir().join(&name).exists() {
+            return Err(Error::AlreadyLinked { name });
+        }
+
+        let node_binary = if cfg!(windows) {
+            self.prefix.join("node.exe")
+        } else {
+            self.prefix.join("bin").join("node")
+        };
+        if !node_binary.is_file() {
+            return Err(Error::NotANodePrefix {
+                prefix: self.prefix,
+            });
+        }
+        let prefix = self.prefix.canonicalize()?;
+
+        crate::linked::register(config, &name, &prefix)
+            .map_err(|source| Error::CantRegister { source })?;
+        outln!(
+            config,
+            Info,
+            "Linked {} to {}",
+            name.cyan(),
+            prefix.display()
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IoError {
+        #[from]
+        source: std::io::Error,
+    },
+    #[error(
+        "{} can't be used as a name, as it reads as a version, a reserved alias or an architecture. Use a name like custom-asan",
+        name
+    )]
+    InvalidName { name: String },
+    #[error("An alias named {} already exists", name)]
+    AliasExists { name: String },
+    #[error("A version named {} is already linked. Uninstall it first to link it again", name)]
+    AlreadyLinked { name: String },
+    #[error("{} doesn't contain a Node installation", prefix.display())]
+    NotANodePrefix { prefix: PathBuf },
+    #[error("Unable to register the linked version: {}", source)]
+    CantRegister { source: std::io::Error },
+}
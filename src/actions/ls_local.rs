@@ -1,31 +1,76 @@
-use crate::alias::{list_aliases, StoredAlias};
+use crate::alias::StoredAlias;
 use crate::app_config::AppConfig;
+use crate::cpu_arch::CPUArch;
+use crate::disk_usage::{dir_size, human_size};
+use crate::release_schedule::{EolStatus, Schedule};
 use crate::version::Version;
-use crate::version_now::version_now;
+use crate::version_record::{LocalState, VersionRecord};
 use colored::Colorize;
-use std::collections::HashMap;
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(clap::Parser, Debug)]
-pub struct ShowLocal {}
+pub struct ShowLocal {
+    /// Show the disk usage and install date of every installed version
+    #[clap(long)]
+    size: bool,
+
+    /// Print the versions as a JSON array. See the README for the schema.
+    #[clap(long)]
+    json: bool,
+}
+
+/// A `show-local --json` entry. The usage keys are only present with `--size`.
+#[derive(Serialize)]
+struct LocalRecord {
+    #[serde(flatten)]
+    record: VersionRecord,
+    #[serde(flatten)]
+    usage: Option<InstallUsage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cpu_archs: Vec<&'static str>,
+}
 
 impl super::command::Command for ShowLocal {
     type Error = Error;
 
     fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
-        let base_dir = config.installations_dir();
-        let mut versions = crate::available_versions::list(base_dir)
+        let mut versions = crate::available_versions::list(config)
             .map_err(|source| Error::CantListLocallyInstalledVersion { source })?;
         versions.insert(0, Version::Bypassed);
         versions.sort();
-        let aliases_hash =
-            generate_aliases_hash(config).map_err(|source| Error::CantReadAliases { source })?;
-        let curr_version = version_now(config).ok().flatten();
+        let local_state =
+            LocalState::load(config).map_err(|source| Error::CantReadAliases { source })?;
+        let schedule = Schedule::load_offline(config);
+        let mut total_size = 0;
+        let mut records = vec![];
 
         for version in versions {
-            let version_aliases = match aliases_hash.get(&version.v_str()) {
-                None => String::new(),
-                Some(versions) => {
+            let cpu_archs: Vec<_> = crate::arch_installs::installed_archs(config, &version)
+                .into_iter()
+                .map(CPUArch::as_str)
+                .collect();
+            let usage = match (&version, self.size) {
+                (Version::Semver(_), true) => {
+                    let usage = InstallUsage::of(&version, config);
+                    total_size += usage.size;
+                    Some(usage)
+                }
+                _ => None,
+            };
+
+            if self.json {
+                records.push(LocalRecord {
+                    record: local_state.record(&version, None, true, config),
+                    usage,
+                    cpu_archs,
+                });
+                continue;
+            }
+
+            let version_aliases = match local_state.aliases_for(&version) {
+                [] => String::new(),
+                versions => {
                     let version_string = versions
                         .iter()
                         .map(StoredAlias::name)
@@ -35,29 +80,94 @@ impl super::command::Command for ShowLocal {
                 }
             };
 
-            let version_str = format!("* {version}{version_aliases}");
+            let version_archs = if cpu_archs.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", format!("[{}]", cpu_archs.join(", ")).dimmed())
+            };
+
+            let version_usage = match usage {
+                Some(usage) => format!(" {}", usage.to_string().dimmed()),
+                None => String::new(),
+            };
+
+            let version_link = match &version {
+                Version::Linked(name) => crate::linked::target(config, name)
+                    .map(|prefix| format!(" {}", format!("-> {}", prefix.display()).dimmed()))
+                    .unwrap_or_default(),
+                _ => String::new(),
+            };
+
+            let version_eol = match schedule.status(&version, config) {
+                Some(EolStatus::EndOfLife { .. }) => format!(" {}", "end-of-life".red()),
+                Some(EolStatus::EndingSoon { end }) => {
+                    format!(" {}", format!("end-of-life on {end}").yellow())
+                }
+                Some(EolStatus::Supported { .. }) | None => String::new(),
+            };
+
+            let version_str =
+                format!("* {version}{version_archs}{version_link}{version_aliases}{version_usage}{version_eol}");
 
-            if curr_version == Some(version) {
+            if local_state.current() == Some(&version) {
                 println!("{}", version_str.cyan());
             } else {
                 println!("{version_str}");
             }
         }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&records).unwrap());
+        } else if self.size {
+            println!("Total: {}", human_size(total_size).bold());
+        }
         Ok(())
     }
 }
 
-fn generate_aliases_hash(config: &AppConfig) -> std::io::Result<HashMap<String, Vec<StoredAlias>>> {
-    let mut aliases = list_aliases(config)?;
-    let mut hashmap: HashMap<String, Vec<StoredAlias>> = HashMap::with_capacity(aliases.len());
-    for alias in aliases.drain(..) {
-        if let Some(value) = hashmap.get_mut(alias.s_ver()) {
-            value.push(alias);
+#[derive(Serialize)]
+struct InstallUsage {
+    #[serde(rename = "size_bytes")]
+    size: u64,
+    #[serde(rename = "global_modules_size_bytes")]
+    global_modules_size: u64,
+    installed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl InstallUsage {
+    fn of(version: &Version, config: &AppConfig) -> Self {
+        let root_path = config.installations_dir().join(version.v_str());
+        let global_modules_path = if cfg!(windows) {
+            root_path.join("installation").join("node_modules")
         } else {
-            hashmap.insert(alias.s_ver().into(), vec![alias]);
+            root_path.join("installation").join("lib").join("node_modules")
+        };
+        let installed_at = std::fs::metadata(&root_path)
+            .and_then(|metadata| metadata.created().or_else(|_| metadata.modified()))
+            .ok()
+            .map(chrono::DateTime::from);
+
+        Self {
+            size: dir_size(&root_path).unwrap_or(0),
+            global_modules_size: dir_size(global_modules_path).unwrap_or(0),
+            installed_at,
+        }
+    }
+}
+
+impl std::fmt::Display for InstallUsage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, global modules {}",
+            human_size(self.size),
+            human_size(self.global_modules_size)
+        )?;
+        if let Some(installed_at) = self.installed_at {
+            write!(f, ", installed {}", installed_at.format("%Y-%m-%d"))?;
         }
+        f.write_str(")")
     }
-    Ok(hashmap)
 }
 
 #[derive(Debug, Error)]
@@ -1,21 +1,59 @@
 use crate::app_config::AppConfig;
-use crate::ni_remote;
+use crate::channel::Channel;
+use crate::ni_remote::{self, IndexedNodeVersion};
+use crate::release_schedule::{EolStatus, Schedule};
 use crate::user_version::UserVersion;
+use crate::version::Version;
+use crate::version_record::LocalState;
 
 use colored::Colorize;
 use thiserror::Error;
 
 #[derive(clap::Parser, Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ShowRemote {
     /// Filter versions by a user-defined version or a semver range
     #[arg(long)]
     filter: Option<UserVersion>,
 
-    /// Show only LTS versions (optionally filter by LTS codename)  
+    /// Show only LTS versions (optionally filter by LTS codename)
     #[arg(long)]
     #[allow(clippy::option_option)]
     lts: Option<Option<String>>,
 
+    /// Show only versions released on or after a date, like 2024-01-31.
+    /// Versions the mirror lists without a date are left out
+    #[arg(long, value_name = "DATE")]
+    since: Option<chrono::NaiveDate>,
+
+    /// Show only versions flagged as security releases
+    #[arg(long)]
+    security_only: bool,
+
+    /// Show only versions bundling an npm version matching a semver range, like ">=10"
+    #[arg(long, value_name = "RANGE")]
+    npm: Option<node_semver::Range>,
+
+    /// Mark versions that are installed locally
+    #[arg(long)]
+    installed: bool,
+
+    /// Only show the newest version of every group
+    #[arg(long, value_name = "GROUP")]
+    group_by: Option<GroupBy>,
+
+    /// Show the end-of-life date of every version's release line
+    #[arg(long)]
+    eol: bool,
+
+    /// Extra columns to show, separated by commas
+    #[arg(long, value_delimiter = ',')]
+    columns: Vec<Column>,
+
+    /// The release channel to list versions from
+    #[arg(long, default_value_t)]
+    channel: Channel,
+
     /// Version sorting order
     #[arg(long, default_value = "asc")]
     sort: SortingMethod,
@@ -23,6 +61,10 @@ pub struct ShowRemote {
     /// Only show the latest matching version
     #[arg(long)]
     latest: bool,
+
+    /// Print the versions as a JSON array. See the README for the schema.
+    #[arg(long)]
+    json: bool,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
@@ -35,6 +77,44 @@ pub enum SortingMethod {
     Ascending,
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum GroupBy {
+    /// Keep only the newest version of every major
+    Major,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    /// Release date
+    Date,
+    /// Bundled npm version
+    Npm,
+    /// Bundled V8 version
+    V8,
+    /// Bundled OpenSSL version
+    Openssl,
+    /// `NODE_MODULE_VERSION` ABI number
+    Modules,
+    /// Whether the release contains security fixes
+    Security,
+    /// Platforms the release was published for
+    Files,
+}
+
+impl Column {
+    fn value(self, version: &IndexedNodeVersion) -> Option<String> {
+        match self {
+            Self::Date => version.date.map(|date| date.to_string()),
+            Self::Npm => version.npm.as_ref().map(|v| format!("npm {v}")),
+            Self::V8 => version.v8.as_ref().map(|v| format!("v8 {v}")),
+            Self::Openssl => version.openssl.as_ref().map(|v| format!("openssl {v}")),
+            Self::Modules => version.modules.as_ref().map(|v| format!("modules {v}")),
+            Self::Security => version.security.then(|| String::from("security")),
+            Self::Files => Some(version.files.join(",")),
+        }
+    }
+}
+
 /// Drain all elements but the last one
 fn truncate_except_latest<T>(list: &mut Vec<T>) {
     let len = list.len();
@@ -44,11 +124,35 @@ fn truncate_except_latest<T>(list: &mut Vec<T>) {
     }
 }
 
+/// Keep only the last version of every major. Expects the list to be sorted.
+fn retain_latest_of_major(list: &mut Vec<IndexedNodeVersion>) {
+    let mut latest: Vec<IndexedNodeVersion> = Vec::with_capacity(list.len());
+    for version in list.drain(..) {
+        let same_major = match (latest.last().map(|v| &v.version), &version.version) {
+            (Some(Version::Semver(last)), Version::Semver(current)) => last.major == current.major,
+            _ => false,
+        };
+        if same_major {
+            latest.pop();
+        }
+        latest.push(version);
+    }
+    *list = latest;
+}
+
+fn npm_matches(version: &IndexedNodeVersion, range: &node_semver::Range) -> bool {
+    version
+        .npm
+        .as_deref()
+        .and_then(|npm| node_semver::Version::parse(npm).ok())
+        .is_some_and(|npm| range.satisfies(&npm))
+}
+
 impl super::command::Command for ShowRemote {
     type Error = Error;
 
     fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
-        let mut all_versions = ni_remote::list(&config.node_dist_mirror)?;
+        let mut all_versions = ni_remote::list(&self.channel.dist_mirrors(config.cpu_arch, config), config)?;
 
         if let Some(lts) = &self.lts {
             match lts {
@@ -65,6 +169,22 @@ impl super::command::Command for ShowRemote {
             all_versions.retain(|v| filter.matches(&v.version, config));
         }
 
+        if let Some(since) = self.since {
+            all_versions.retain(|v| v.date.is_some_and(|date| date >= since));
+        }
+
+        if self.security_only {
+            all_versions.retain(|v| v.security);
+        }
+
+        if let Some(range) = &self.npm {
+            all_versions.retain(|v| npm_matches(v, range));
+        }
+
+        if let Some(GroupBy::Major) = self.group_by {
+            retain_latest_of_major(&mut all_versions);
+        }
+
         if self.latest {
             truncate_except_latest(&mut all_versions);
         }
@@ -73,16 +193,55 @@ impl super::command::Command for ShowRemote {
             all_versions.reverse();
         }
 
+        let installed_versions = if self.json || self.installed {
+            crate::available_versions::list(config)
+                .map_err(|source| Error::CantListLocallyInstalledVersion { source })?
+        } else {
+            vec![]
+        };
+
+        if self.json {
+            let local_state =
+                LocalState::load(config).map_err(|source| Error::CantReadAliases { source })?;
+            let records: Vec<_> = all_versions
+                .iter()
+                .map(|v| {
+                    let installed = installed_versions.contains(&v.version);
+                    local_state.record(&v.version, v.lts.as_deref(), installed, config)
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&records).unwrap());
+            return Ok(());
+        }
+
         if all_versions.is_empty() {
             eprintln!("{}", "No versions were found!".red());
             return Ok(());
         }
 
+        let schedule = self.eol.then(|| Schedule::load(config));
+
         for version in &all_versions {
             print!("{}", version.version);
             if let Some(lts) = &version.lts {
                 print!("{}", format!(" ({lts})").cyan());
             }
+            for column in &self.columns {
+                if let Some(value) = column.value(version) {
+                    print!("  {}", value.dimmed());
+                }
+            }
+            if let Some(status) = schedule.as_ref().and_then(|s| s.status(&version.version, config)) {
+                let eol = format!("eol {}", status.end());
+                match status {
+                    EolStatus::EndOfLife { .. } => print!("  {}", eol.red()),
+                    EolStatus::EndingSoon { .. } => print!("  {}", eol.yellow()),
+                    EolStatus::Supported { .. } => print!("  {}", eol.dimmed()),
+                }
+            }
+            if self.installed && installed_versions.contains(&version.version) {
+                print!("  {}", "installed".green());
+            }
             println!();
         }
 
@@ -97,4 +256,10 @@ pub enum Error {
         #[from]
         source: crate::http::Error,
     },
+    #[error("Unable to list locally installed versions: {}", source)]
+    CantListLocallyInstalledVersion {
+        source: crate::available_versions::Error,
+    },
+    #[error("Unable to read aliases: {}", source)]
+    CantReadAliases { source: std::io::Error },
 }
@@ -0,0 +1,425 @@
+use super::command::Command;
+use crate::app_config::AppConfig;
+use crate::checksums::{sha256_file, Shasums};
+use crate::http::redact;
+use crate::loaders::{ProgressConfig, ResponseProgress};
+use crate::outln;
+use colored::Colorize;
+use indicatif::ProgressDrawTarget;
+use log::debug;
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use url::Url;
+
+#[derive(clap::Parser, Debug)]
+pub struct Mirror {
+    #[clap(subcommand)]
+    command: MirrorCommand,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum MirrorCommand {
+    /// Download releases into a directory laid out like nodejs.org/dist,
+    /// which `ENM_NODE_DIST_MIRROR` can point at.
+    ///
+    /// Ex: `enm mirror sync --dest /srv/node-dist --versions ">=18" --platforms linux-x64,linux-arm64`
+    ///
+    /// Files which are already in the directory with a matching checksum are not downloaded again.
+    Sync(MirrorSync),
+}
+
+#[derive(clap::Args, Debug)]
+struct MirrorSync {
+    /// The mirror directory to write to
+    #[arg(long, value_name = "DIR")]
+    dest: PathBuf,
+
+    /// Only mirror versions matching a semver range, like ">=18"
+    #[arg(long, value_name = "RANGE")]
+    versions: Option<node_semver::Range>,
+
+    /// The platforms to mirror archives for, separated by commas, like linux-x64,darwin-arm64,win-x64
+    #[arg(long, value_delimiter = ',', required = true)]
+    platforms: Vec<DistPlatform>,
+
+    /// Also mirror the headers of every version, for `enm install --with-headers`
+    #[arg(long)]
+    with_headers: bool,
+
+    /// Also mirror the source tarball of every version, for `enm install --from-source`
+    #[arg(long)]
+    with_source: bool,
+
+    /// Show an interactive loaders bar for the downloads
+    #[arg(long, default_value_t)]
+    #[arg(value_enum)]
+    loaders: ProgressConfig,
+}
+
+/// A platform in the dist layout, like `linux-x64`
+#[derive(Debug, Clone)]
+struct DistPlatform {
+    os: String,
+    arch: String,
+}
+
+impl std::str::FromStr for DistPlatform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (os, arch) = s
+            .split_once('-')
+            .filter(|(os, arch)| !os.is_empty() && !arch.is_empty())
+            .ok_or_else(|| format!("{s} is not a platform like linux-x64"))?;
+        let os = match os {
+            "osx" | "macos" => "darwin",
+            "windows" => "win",
+            os => os,
+        };
+        Ok(Self {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        })
+    }
+}
+
+impl DistPlatform {
+    /// The name of this platform in the `files` key of `index.json`
+    fn index_key(&self) -> String {
+        match self.os.as_str() {
+            "darwin" => format!("osx-{}-tar", self.arch),
+            "win" => format!("win-{}-zip", self.arch),
+            os => format!("{os}-{}", self.arch),
+        }
+    }
+
+    /// Whether `file_name` is an archive of `version` for this platform
+    fn has_archive(&self, version: &str, file_name: &str) -> bool {
+        let prefix = format!("node-{version}-{}-{}.", self.os, self.arch);
+        file_name
+            .strip_prefix(&prefix)
+            .is_some_and(|ext| matches!(ext, "tar.xz" | "tar.gz" | "zip"))
+    }
+
+    /// The library native addons link against on Windows, which comes with the headers
+    fn node_lib(&self) -> Option<String> {
+        (self.os == "win").then(|| format!("win-{}/node.lib", self.arch))
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    versions: usize,
+    downloaded: usize,
+    up_to_date: usize,
+}
+
+impl Command for Mirror {
+    type Error = Error;
+
+    fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        match self.command {
+            MirrorCommand::Sync(sync) => sync.apply(config),
+        }
+    }
+}
+
+impl MirrorSync {
+    fn apply(self, config: &AppConfig) -> Result<(), Error> {
+        let show_loaders = self.loaders.enabled(config);
+        let (index, mirror) = crate::mirrors::try_in_order(
+            &config.node_dist_mirrors(),
+            config,
+            crate::http::Error::is_outage,
+            |mirror| {
+                crate::http::get(&dist_url(mirror, "index.json"))?
+                    .error_for_status()?
+                    .json::<Vec<Value>>()
+            },
+        )
+        .map_err(|source| Error::CantFetchIndex { source })?;
+        debug!("Syncing from {}", redact(&mirror));
+
+        let index: Vec<_> = index
+            .into_iter()
+            .filter_map(|entry| self.select(entry))
+            .collect();
+        let mut summary = Summary::default();
+
+        for entry in &index {
+            let version = entry["version"].as_str().unwrap_or_default();
+            if self.is_synced(entry) {
+                debug!("{} is up to date", version);
+                summary.up_to_date += 1;
+            } else {
+                self.sync_version(&mirror, version, show_loaders, &mut summary, config)?;
+            }
+            summary.versions += 1;
+        }
+
+        write_file(&self.dest.join("index.json"), &index_json(&index))?;
+        write_file(&self.dest.join("index.tab"), &index_tab(&index))?;
+
+        outln!(
+            config,
+            Info,
+            "Synced {} version(s) into {}: {} file(s) downloaded, {} up to date",
+            summary.versions,
+            self.dest.display(),
+            summary.downloaded,
+            summary.up_to_date
+        );
+        Ok(())
+    }
+
+    /// Keeps an `index.json` entry when its version is in range and it is
+    /// published for any of the platforms, leaving only their `files`.
+    fn select(&self, mut entry: Value) -> Option<Value> {
+        let version = entry["version"].as_str()?;
+        let semver = node_semver::Version::parse(version.trim_start_matches('v')).ok()?;
+        if !self
+            .versions
+            .as_ref()
+            .map_or(true, |range| range.satisfies(&semver))
+        {
+            return None;
+        }
+
+        let keys: Vec<String> = self.platforms.iter().map(DistPlatform::index_key).collect();
+        let files = entry.get_mut("files")?.as_array_mut()?;
+        files.retain(|file| {
+            file.as_str()
+                .is_some_and(|file| keys.iter().any(|key| key == file))
+        });
+        if files.is_empty() {
+            return None;
+        }
+        Some(entry)
+    }
+
+    /// Whether a file of `version` is mirrored, based on its name in `SHASUMS256.txt`
+    fn wants(&self, version: &str, file_name: &str) -> bool {
+        self.platforms.iter().any(|platform| {
+            platform.has_archive(version, file_name)
+                || (self.with_headers && platform.node_lib().as_deref() == Some(file_name))
+        }) || (self.with_headers && file_name == format!("node-{version}-headers.tar.gz"))
+            || (self.with_source && file_name == format!("node-{version}.tar.gz"))
+    }
+
+    /// Whether every file an `index.json` entry needs was mirrored by a previous sync,
+    /// so its `SHASUMS256.txt` doesn't have to be fetched again. The files were
+    /// verified when they were downloaded, and are listed in the local `SHASUMS256.txt`.
+    fn is_synced(&self, entry: &Value) -> bool {
+        let version = entry["version"].as_str().unwrap_or_default();
+        let version_dir = self.dest.join(version);
+        let Ok(synced) = Shasums::read(&version_dir.join("SHASUMS256.txt")) else {
+            return false;
+        };
+        if !synced
+            .iter()
+            .all(|(_, file_name)| version_dir.join(file_name).is_file())
+        {
+            return false;
+        }
+
+        let files: Vec<&str> = entry["files"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        let has = |file_name: &str| synced.get(file_name).is_some();
+        let has_platforms = self
+            .platforms
+            .iter()
+            .filter(|platform| files.contains(&platform.index_key().as_str()))
+            .all(|platform| {
+                synced
+                    .iter()
+                    .any(|(_, file_name)| platform.has_archive(version, file_name))
+                    && (!self.with_headers || platform.node_lib().map_or(true, |lib| has(&lib)))
+            });
+        has_platforms
+            && (!self.with_headers || has(&format!("node-{version}-headers.tar.gz")))
+            && (!self.with_source || has(&format!("node-{version}.tar.gz")))
+    }
+
+    fn sync_version(
+        &self,
+        mirror: &Url,
+        version: &str,
+        show_loaders: bool,
+        summary: &mut Summary,
+        config: &AppConfig,
+    ) -> Result<(), Error> {
+        let version_dir = self.dest.join(version);
+        std::fs::create_dir_all(&version_dir).map_err(|source| Error::CantWriteMirror {
+            path: version_dir.clone(),
+            source,
+        })?;
+
+        let shasums_url = dist_url(mirror, &format!("{version}/SHASUMS256.txt"));
+        let mut shasums = crate::http::get(&shasums_url)
+            .and_then(crate::http::Response::error_for_status)
+            .and_then(crate::http::Response::text)
+            .map(|text| Shasums::parse(&text))
+            .map_err(|source| Error::CantFetchChecksums {
+                version: version.to_string(),
+                source,
+            })?;
+        shasums.retain(|file_name| self.wants(version, file_name));
+
+        let shasums_path = version_dir.join("SHASUMS256.txt");
+        let synced = Shasums::read(&shasums_path).unwrap_or_default();
+
+        for (checksum, file_name) in shasums.iter() {
+            let path = version_dir.join(file_name);
+            let is_synced = synced.get(file_name) == Some(checksum)
+                || sha256_file(&path).is_ok_and(|actual| actual == checksum);
+            if path.exists() && is_synced {
+                debug!("{} is up to date", path.display());
+                summary.up_to_date += 1;
+                continue;
+            }
+
+            outln!(
+                config,
+                Info,
+                "Downloading {}",
+                format!("{version}/{file_name}").cyan()
+            );
+            let url = dist_url(mirror, &format!("{version}/{file_name}"));
+            download(&url, &path, checksum, show_loaders)?;
+            summary.downloaded += 1;
+        }
+
+        write_file(&shasums_path, &shasums.to_string())
+    }
+}
+
+fn dist_url(mirror: &Url, path: &str) -> String {
+    format!("{}/{}", mirror.as_str().trim_end_matches('/'), path)
+}
+
+/// Downloads `url` into `path`, making sure it matches `checksum` before
+/// replacing whatever is there.
+fn download(url: &str, path: &Path, checksum: &str, show_loaders: bool) -> Result<(), Error> {
+    let file_name = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let response = crate::http::get(url)
+        .and_then(crate::http::Response::error_for_status)
+        .map_err(|source| Error::CantDownload {
+            file_name: file_name.clone(),
+            source,
+        })?;
+
+    let cant_write = |source| Error::CantWriteMirror {
+        path: path.to_path_buf(),
+        source,
+    };
+    let parent = path.parent().expect("Archives are in a version directory");
+    // `node.lib` is in a directory per architecture
+    std::fs::create_dir_all(parent).map_err(cant_write)?;
+    let mut temp_file = tempfile::NamedTempFile::new_in(parent).map_err(cant_write)?;
+    if show_loaders {
+        let mut progress = ResponseProgress::new(response, ProgressDrawTarget::stderr());
+        std::io::copy(&mut progress, &mut temp_file).map_err(cant_write)?;
+    } else {
+        let mut response = response;
+        std::io::copy(&mut response, &mut temp_file).map_err(cant_write)?;
+    }
+    temp_file.flush().map_err(cant_write)?;
+
+    let actual = sha256_file(temp_file.path()).map_err(cant_write)?;
+    if actual != checksum {
+        return Err(Error::ChecksumMismatch {
+            file_name,
+            expected: checksum.to_string(),
+            actual,
+        });
+    }
+
+    temp_file
+        .persist(path)
+        .map_err(|err| cant_write(err.error))?;
+    Ok(())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), Error> {
+    std::fs::write(path, contents).map_err(|source| Error::CantWriteMirror {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Writes one entry per line, like nodejs.org does
+fn index_json(index: &[Value]) -> String {
+    let entries: Vec<String> = index.iter().map(Value::to_string).collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+fn index_tab(index: &[Value]) -> String {
+    const COLUMNS: [&str; 11] = [
+        "version", "date", "files", "npm", "v8", "uv", "zlib", "openssl", "modules", "lts",
+        "security",
+    ];
+
+    let mut tab = COLUMNS.join("\t");
+    tab.push('\n');
+    for entry in index {
+        let row: Vec<String> = COLUMNS
+            .iter()
+            .map(|column| match &entry[*column] {
+                Value::String(value) => value.clone(),
+                Value::Array(files) => files
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(","),
+                Value::Bool(true) => String::from("true"),
+                Value::Bool(false) if *column == "security" => String::from("false"),
+                _ => String::from("-"),
+            })
+            .collect();
+        tab.push_str(&row.join("\t"));
+        tab.push('\n');
+    }
+    tab
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Unable to fetch the upstream index: {}", source)]
+    CantFetchIndex { source: crate::http::Error },
+    #[error("Unable to fetch the checksums of {}: {}", version, source)]
+    CantFetchChecksums {
+        version: String,
+        source: crate::http::Error,
+    },
+    #[error("Unable to download {}: {}", file_name, source)]
+    CantDownload {
+        file_name: String,
+        source: crate::http::Error,
+    },
+    #[error("Unable to write {}: {}", path.display(), source)]
+    CantWriteMirror {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error(
+        "The checksum of {} doesn't match SHASUMS256.txt: expected {}, got {}",
+        file_name,
+        expected,
+        actual
+    )]
+    ChecksumMismatch {
+        file_name: String,
+        expected: String,
+        actual: String,
+    },
+}
@@ -1,12 +1,18 @@
 pub mod alias;
+pub mod clean_shims;
 pub mod command;
 pub mod default;
+pub mod fetch;
 pub mod app_van;
 pub mod runx;
 pub mod in_use;
 pub mod firstrun;
+pub mod import;
+pub mod link;
 pub mod ls_local;
 pub mod ls_remote;
+pub mod mirror;
+pub mod outdated;
 pub mod unalias;
 pub mod uninstall;
 pub mod switch;
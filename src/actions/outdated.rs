@@ -0,0 +1,121 @@
+use super::command::Command;
+use crate::alias::list_aliases;
+use crate::app_config::AppConfig;
+use crate::available_versions;
+use crate::channel::Channel;
+use crate::ni_remote::{self, IndexedNodeVersion};
+use crate::outln;
+use crate::version::Version;
+use colored::Colorize;
+use thiserror::Error;
+
+#[derive(clap::Parser, Debug)]
+pub struct Outdated {}
+
+struct Report<'a> {
+    newest: Option<&'a IndexedNodeVersion>,
+    security: bool,
+}
+
+impl<'a> Report<'a> {
+    /// Finds the newest release in the same major as `version`, and whether
+    /// any release after `version` up to it is flagged as a security release.
+    fn of(version: &node_semver::Version, remote_versions: &'a [IndexedNodeVersion]) -> Self {
+        let newer_in_major: Vec<_> = remote_versions
+            .iter()
+            .filter(|x| match &x.version {
+                Version::Semver(v) => v.major == version.major && v > version,
+                _ => false,
+            })
+            .collect();
+
+        Self {
+            newest: newer_in_major.last().copied(),
+            security: newer_in_major.iter().any(|x| x.security),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match (self.newest, self.security) {
+            (None, _) => format!("{}", "is up to date".dimmed()),
+            (Some(newest), false) => format!("can be updated to {}", newest.version.v_str().cyan()),
+            (Some(newest), true) => format!(
+                "can be updated to {} {}",
+                newest.version.v_str().cyan(),
+                "(security update)".red().bold()
+            ),
+        }
+    }
+}
+
+impl Command for Outdated {
+    type Error = Error;
+
+    fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        let mut installed_versions = available_versions::list(config)
+            .map_err(|source| Error::VersionListingError { source })?;
+        installed_versions.sort();
+        let dist_mirrors = Channel::Release.dist_mirrors(config.cpu_arch, config);
+        let remote_versions = ni_remote::list(&dist_mirrors, config)
+            .map_err(|source| Error::CantListRemoteVersions { source })?;
+        let mut aliases =
+            list_aliases(config).map_err(|source| Error::CantReadAliases { source })?;
+        aliases.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let mut pending_security_updates = 0;
+
+        for version in &installed_versions {
+            if let Version::Semver(semver) = version {
+                let report = Report::of(semver, &remote_versions);
+                if report.security {
+                    pending_security_updates += 1;
+                }
+                outln!(config, Info, "* {} {}", version.v_str(), report.describe());
+            }
+        }
+
+        for alias in &aliases {
+            if let Ok(Version::Semver(semver)) = Version::parse(alias.s_ver()) {
+                let report = Report::of(&semver, &remote_versions);
+                outln!(
+                    config,
+                    Info,
+                    "* alias {} (v{}) {}",
+                    alias.name().cyan(),
+                    semver,
+                    report.describe()
+                );
+            }
+        }
+
+        if pending_security_updates > 0 {
+            return Err(Error::SecurityUpdatesPending {
+                count: pending_security_updates,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn handle_error(err: Self::Error, config: &AppConfig) {
+        let err_s = format!("{err}");
+        outln!(config, Error, "{} {}", "error:".red().bold(), err_s.red());
+        let code = match err {
+            Error::SecurityUpdatesPending { .. } => 2,
+            _ => 1,
+        };
+        std::process::exit(code);
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Unable to get locally installed versions: {}", source)]
+    VersionListingError { source: available_versions::Error },
+    #[error("Having a hard time listing the remote versions: {}", source)]
+    CantListRemoteVersions { source: crate::http::Error },
+    #[error("Unable to read aliases: {}", source)]
+    CantReadAliases { source: std::io::Error },
+    #[error("{} installed version(s) have a pending security update", count)]
+    SecurityUpdatesPending { count: usize },
+}
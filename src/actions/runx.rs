@@ -88,7 +88,13 @@ impl Cmd for RunX {
 
         log::debug!("Running {} with PATH={:?}", binary, path_env);
 
-        let exit_status = Command::new(binary)
+        let mut command = Command::new(binary);
+        if let Some(nodedir) = node_headers_dir(applicable_version.path()) {
+            log::debug!("Building native addons with the headers in {:?}", nodedir);
+            command.env("npm_config_nodedir", nodedir);
+        }
+
+        let exit_status = command
             .args(arguments)
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
@@ -107,6 +113,13 @@ impl Cmd for RunX {
     }
 }
 
+/// The directory node-gyp should take the headers from, which is the
+/// installation itself when it has them, unless the user set their own.
+fn node_headers_dir(installation: &std::path::Path) -> Option<&std::path::Path> {
+    let user_nodedir = std::env::var_os("npm_config_nodedir").is_some();
+    (crate::node_headers::has_headers(installation) && !user_nodedir).then_some(installation)
+}
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Unable to spawn program: {source}\nMaybe the program {} does not exist on not available in PATH?", binary.bold())]
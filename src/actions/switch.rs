@@ -1,16 +1,19 @@
 use super::command::Command;
 use super::firstrun::FirstRun;
+use crate::arch_installs;
 use crate::available_versions;
 use crate::symlinked;
 use crate::outln;
+use crate::release_schedule::Schedule;
+use crate::resolution_cache::ResolutionCache;
 use crate::terminators_entry;
 use crate::sift_method::SiftMethod;
 use crate::machine_semver;
 use crate::user_version::UserVersion;
 use crate::version::Version;
-use crate::version_now::version_now;
 use crate::{app_config::AppConfig, reader_uv::ReaderUV};
 use colored::Colorize;
+use log::debug;
 use std::path::Path;
 use thiserror::Error;
 
@@ -32,7 +35,21 @@ impl Command for Switch {
         let plural_ctx = config.plural_ctx().ok_or(Error::EnmEnvWasNotSourced)?;
         warn_if_plural_ctx_not_in_path_env_var(plural_ctx, config);
 
-        let all_versions = available_versions::list(config.installations_dir())
+        // The shell hook runs this on every `cd`, so it returns right away when
+        // nothing the version of the directory depends on changed
+        let resolution_cache = (self.caps_lock_when_needed && self.version.is_none()).then(|| {
+            let current_dir = std::env::current_dir().unwrap();
+            ResolutionCache::new(plural_ctx, &current_dir, config)
+        });
+        if resolution_cache
+            .as_ref()
+            .is_some_and(|cache| cache.is_fresh(plural_ctx))
+        {
+            debug!("The version of the current directory is unchanged");
+            return Ok(());
+        }
+
+        let all_versions = available_versions::list(config)
             .map_err(|source| Error::VersionListingError { source })?;
         let requested_version = self
             .version
@@ -47,6 +64,7 @@ impl Command for Switch {
             })
             .map_err(|source| Error::CantConjectVersion { source })?;
 
+        let mut resolved_version = None;
         let (message, version_path) = if let UserVersion::Full(Version::Bypassed) =
             requested_version
         {
@@ -67,20 +85,27 @@ impl Command for Switch {
                 (message, system_path)
             } else if alias_path.exists() {
                 let message = format!("Using Node for alias {}", alias_name.cyan());
-                (message, alias_path)
+                let version_path = arch_installs::alias_installation_for(config, &alias_path)?;
+                (message, version_path)
+            } else if crate::linked::is_linked(config, &alias_name) {
+                let message = format!("Using linked Node {}", alias_name.cyan());
+                let version_path = Version::Linked(alias_name).installation_path(config);
+                (message, version_path)
             } else {
                 first_run_new_version(requested_version, config, self.install_if_missing)?;
                 return Ok(());
             }
         } else {
             let version_now = requested_version.to_version(&all_versions, config);
-            if let Some(version) = version_now {
-                let version_path = config
-                    .installations_dir()
-                    .join(version.to_string())
-                    .join("installation");
+            let installation = version_now.map(|version| {
+                (version, arch_installs::installation_for(config, version))
+            });
+            if let Some((version, Ok(version_path))) = installation {
+                resolved_version = Some(version);
                 let message = format!("Using Node {}", version.to_string().cyan());
                 (message, version_path)
+            } else if let Some((_, Err(source))) = installation.filter(|_| !self.install_if_missing) {
+                return Err(Error::NotInstalledForArch { source });
             } else {
                 first_run_new_version(requested_version, config, self.install_if_missing)?;
                 return Ok(());
@@ -89,6 +114,9 @@ impl Command for Switch {
 
         if !self.caps_lock_when_needed || will_version_change(&version_path, config) {
             outln!(config, Info, "{}", message);
+            if let Some(version) = resolved_version {
+                Schedule::load_offline(config).warn_if_eol(version, config);
+            }
         }
 
         if let Some(shim_path) = plural_ctx.parent() {
@@ -99,19 +127,30 @@ impl Command for Switch {
             })?;
         }
 
-        replace_symlink(&version_path, plural_ctx)
+        symlinked::replace_symlink_dir(&version_path, plural_ctx)
             .map_err(|source| Error::SymlinkingCreationIssue { source })?;
+        if let Some(resolution_cache) = resolution_cache {
+            resolution_cache.save(&version_path);
+        }
 
         Ok(())
     }
 }
 
 fn will_version_change(resolved_path: &Path, config: &AppConfig) -> bool {
-    let version_now_path = version_now(config)
-        .unwrap_or(None)
-        .map(|v| v.installation_path(config));
+    let Some(plural_ctx) = config.plural_ctx() else {
+        return true;
+    };
+    if std::fs::read_link(plural_ctx).is_ok_and(|target| target == resolved_path) {
+        return false;
+    }
 
-    version_now_path.as_deref() != Some(resolved_path)
+    // The same installation can be reached through an alias, or the link to
+    // the architecture of a version installed first
+    match (plural_ctx.canonicalize(), resolved_path.canonicalize()) {
+        (Ok(now), Ok(resolved)) => now != resolved,
+        _ => true,
+    }
 }
 
 fn first_run_new_version(
@@ -142,20 +181,6 @@ fn first_run_new_version(
     Ok(())
 }
 
-/// Tries to delete `from`, and then tries to symlink `from` to `to` anyway.
-/// If the symlinking fails, it will return the errors in the following order:
-/// * The deletion error (if exists)
-/// * The creation error
-///
-/// This way, we can create a symlink if it is missing.
-fn replace_symlink(from: &std::path::Path, to: &std::path::Path) -> std::io::Result<()> {
-    let symlink_deletion_result = symlinked::remove_symlink_dir(to);
-    match symlinked::symlink_dir(from, to) {
-        ok @ Ok(()) => ok,
-        err @ Err(_) => symlink_deletion_result.and(err),
-    }
-}
-
 fn should_install_interactively(requested_version: &UserVersion) -> bool {
     use std::io::{IsTerminal, Write};
 
@@ -219,6 +244,11 @@ pub enum Error {
     #[error("Requested NodeJS version {} is not installed in your system", version)]
     CantFindVersion { version: UserVersion },
     #[error(transparent)]
+    NotInstalledForArch {
+        #[from]
+        source: arch_installs::NotInstalled,
+    },
+    #[error(transparent)]
     CantConjectVersion {
         #[from]
         source: ConjectVersionError,
@@ -14,12 +14,16 @@ impl Command for Unalias {
     type Error = Error;
 
     fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
+        let _lock = crate::file_lock::lock_aliases(config)
+            .map_err(|source| Error::CantDeleteSymlink { source })?;
         let requested_version = user_version_in::user_version_in(
             &UserVersion::Full(Version::Alias(self.requested_alias.clone())),
             config,
         )
         .ok()
         .flatten()
+        // Linked versions are unregistered with `enm uninstall`
+        .filter(|version| !matches!(version.version(), Version::Linked(_)))
         .ok_or(Error::AliasNotFound {
             requested_alias: self.requested_alias,
         })?;
@@ -1,6 +1,8 @@
 use super::command::Command;
+use crate::alias::StoredAlias;
 use crate::available_versions;
 use crate::app_config::AppConfig;
+use crate::disk_usage::{dir_size, human_size};
 use crate::symlinked::remove_symlink_dir;
 use crate::outln;
 use crate::user_version::UserVersion;
@@ -12,76 +14,246 @@ use thiserror::Error;
 
 #[derive(clap::Parser, Debug)]
 pub struct Uninstall {
-    version: Option<UserVersion>,
+    /// The versions to uninstall. Defaults to the version found in the current directory
+    versions: Vec<UserVersion>,
+
+    /// Uninstall every installed version matching a partial version, like `16`
+    #[clap(long)]
+    all_matching: bool,
+
+    /// Uninstall every installed version except the provided versions or aliases
+    #[clap(
+        long,
+        value_name = "VERSION",
+        num_args = 1..,
+        conflicts_with_all = &["versions", "all_matching"]
+    )]
+    all_except: Vec<UserVersion>,
+
+    /// Don't ask for confirmation when uninstalling more than one version
+    #[clap(long, short)]
+    yes: bool,
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    versions: Vec<Version>,
+    aliases: usize,
+    reclaimed_bytes: u64,
+}
+
+impl Uninstall {
+    fn requested_versions(
+        &self,
+        all_versions: &[Version],
+        config: &AppConfig,
+    ) -> Result<Vec<Version>, Error> {
+        let requested_versions = if self.versions.is_empty() {
+            let current_dir = std::env::current_dir().unwrap();
+            let version = get_user_version_for_directory(current_dir, config)
+                .ok_or(Error::CantConjectVersion)?;
+            vec![version]
+        } else {
+            self.versions.clone()
+        };
+
+        let mut to_remove = vec![];
+        for requested_version in requested_versions {
+            if matches!(requested_version, UserVersion::Full(Version::Bypassed)) {
+                return Err(Error::CantUninstallSystemVersion);
+            }
+
+            let matched_versions: Vec<&Version> = all_versions
+                .iter()
+                .filter(|v| requested_version.matches(v, config))
+                .collect();
+
+            if matched_versions.is_empty() {
+                return Err(Error::CantFindVersion {
+                    version: requested_version,
+                });
+            }
+
+            if matched_versions.len() >= 2 && !self.all_matching {
+                return Err(Error::PleaseBeMoreSpecificToDelete {
+                    matched_versions: matched_versions
+                        .iter()
+                        .map(std::string::ToString::to_string)
+                        .collect(),
+                });
+            }
+
+            to_remove.extend(matched_versions.into_iter().cloned());
+        }
+
+        Ok(to_remove)
+    }
+
+    fn versions_except(
+        &self,
+        all_versions: &[Version],
+        config: &AppConfig,
+    ) -> Result<Vec<Version>, Error> {
+        for kept_version in &self.all_except {
+            if !all_versions.iter().any(|v| kept_version.matches(v, config)) {
+                return Err(Error::CantFindVersion {
+                    version: kept_version.clone(),
+                });
+            }
+        }
+
+        Ok(all_versions
+            .iter()
+            .filter(|v| !self.all_except.iter().any(|kept| kept.matches(v, config)))
+            .cloned()
+            .collect())
+    }
 }
 
 impl Command for Uninstall {
     type Error = Error;
 
     fn apply(self, config: &AppConfig) -> Result<(), Self::Error> {
-        let all_versions = available_versions::list(config.installations_dir())
+        let all_versions = available_versions::list(config)
             .map_err(|source| Error::VersionListingError { source })?;
-        let requested_version = self
-            .version
-            .or_else(|| {
-                let current_dir = std::env::current_dir().unwrap();
-                get_user_version_for_directory(current_dir, config)
-            })
-            .ok_or(Error::CantConjectVersion)?;
-
-        if matches!(requested_version, UserVersion::Full(Version::Bypassed)) {
-            return Err(Error::CantUninstallSystemVersion);
+
+        let mut to_remove = if self.all_except.is_empty() {
+            self.requested_versions(&all_versions, config)?
+        } else {
+            self.versions_except(&all_versions, config)?
+        };
+        to_remove.sort();
+        to_remove.dedup();
+
+        if to_remove.is_empty() {
+            outln!(config, Info, "No installed versions to uninstall");
+            return Ok(());
         }
 
-        let available_versions: Vec<&Version> = all_versions
-            .iter()
-            .filter(|v| requested_version.matches(v, config))
-            .collect();
-
-        if available_versions.len() >= 2 {
-            return Err(Error::PleaseBeMoreSpecificToDelete {
-                matched_versions: available_versions
-                    .iter()
-                    .map(std::string::ToString::to_string)
-                    .collect(),
-            });
+        let needs_confirmation = to_remove.len() > 1 || !self.all_except.is_empty();
+        if needs_confirmation && !self.yes && !confirm_removal(&to_remove)? {
+            outln!(config, Info, "Nothing was uninstalled");
+            return Ok(());
+        }
+
+        let mut summary = Summary::default();
+        for version in to_remove {
+            remove_version(version, config, &mut summary)?;
         }
 
-        let version = requested_version
-            .to_version(&all_versions, config)
-            .ok_or(Error::CantFindVersion)?;
+        outln!(
+            config,
+            Info,
+            "Removed {} version(s) ({}), {} alias(es), reclaimed {}",
+            summary.versions.len(),
+            summary
+                .versions
+                .iter()
+                .map(Version::v_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+                .cyan(),
+            summary.aliases,
+            human_size(summary.reclaimed_bytes).cyan()
+        );
+
+        Ok(())
+    }
+}
+
+fn remove_version(version: Version, config: &AppConfig, summary: &mut Summary) -> Result<(), Error> {
+    let _lock = crate::file_lock::lock_version(config, &version)?;
+    let matching_aliases = version.find_aliases(config)?;
+    if let Version::Linked(name) = &version {
+        unregister_linked(name, config)?;
+        remove_aliases(&matching_aliases, config)?;
+        summary.versions.push(version);
+        summary.aliases += matching_aliases.len();
+        return Ok(());
+    }
+
+    let root_path = version
+        .root_path(config)
+        .ok_or_else(|| Error::RootPathNotFound {
+            version: version.clone(),
+        })?;
+
+    let size = dir_size(&root_path).unwrap_or_else(|err| {
+        debug!("Unable to measure {:?}: {}", root_path, err);
+        0
+    });
+
+    debug!("Removing Node version from {:?}", root_path);
+    std::fs::remove_dir_all(root_path)
+        .map_err(|source| Error::CantDeleteNodeVersion { source })?;
+    outln!(
+        config,
+        Info,
+        "Node version {} was removed successfully",
+        version.v_str().cyan()
+    );
 
-        let matching_aliases = version.find_aliases(config)?;
-        let root_path = version
-            .root_path(config)
-            .ok_or_else(|| Error::RootPathNotFound {
-                version: version.clone(),
-            })?;
+    remove_aliases(&matching_aliases, config)?;
 
-        debug!("Removing Node version from {:?}", root_path);
-        std::fs::remove_dir_all(root_path)
-            .map_err(|source| Error::CantDeleteNodeVersion { source })?;
+    summary.versions.push(version);
+    summary.aliases += matching_aliases.len();
+    summary.reclaimed_bytes += size;
+
+    Ok(())
+}
+
+/// Linked versions aren't owned by enm, so only the link to them is removed
+fn unregister_linked(name: &str, config: &AppConfig) -> Result<(), Error> {
+    let prefix = crate::linked::target(config, name)?;
+    debug!("Unregistering linked version {} at {:?}", name, prefix);
+    crate::linked::unregister(config, name)
+        .map_err(|source| Error::CantDeleteNodeVersion { source })?;
+    outln!(
+        config,
+        Info,
+        "Linked version {} was unregistered, {} was left untouched",
+        name.cyan(),
+        prefix.display()
+    );
+    Ok(())
+}
+
+fn remove_aliases(aliases: &[StoredAlias], config: &AppConfig) -> Result<(), Error> {
+    let _lock = crate::file_lock::lock_aliases(config)?;
+    for alias in aliases {
+        debug!("Removing alias from {:?}", alias.path());
+        remove_symlink_dir(alias.path())
+            .map_err(|source| Error::CantDeleteSymlink { source })?;
         outln!(
             config,
             Info,
-            "Node version {} was removed successfully",
-            version.v_str().cyan()
+            "Alias {} was removed successfully",
+            alias.name().cyan()
         );
+    }
+    Ok(())
+}
 
-        for alias in matching_aliases {
-            debug!("Removing alias from {:?}", alias.path());
-            remove_symlink_dir(alias.path())
-                .map_err(|source| Error::CantDeleteSymlink { source })?;
-            outln!(
-                config,
-                Info,
-                "Alias {} was removed successfully",
-                alias.name().cyan()
-            );
-        }
+fn confirm_removal(versions: &[Version]) -> Result<bool, Error> {
+    use std::io::{IsTerminal, Write};
 
-        Ok(())
+    if !(std::io::stdout().is_terminal() && std::io::stdin().is_terminal()) {
+        return Err(Error::ConfirmationRequired);
     }
+
+    eprintln!("{}", "The following versions will be uninstalled:".yellow());
+    for version in versions {
+        eprintln!("* {version}");
+    }
+    let do_you_want = format!("Type y to uninstall {} version(s) [y/N]:", versions.len());
+    eprint!("{} ", do_you_want.cyan());
+    std::io::stdout().flush().unwrap();
+    let mut s = String::new();
+    std::io::stdin()
+        .read_line(&mut s)
+        .expect("Unable to read user input");
+
+    Ok(s.trim().to_lowercase() == "y")
 }
 
 #[derive(Debug, Error)]
@@ -92,10 +264,12 @@ pub enum Error {
     CantConjectVersion,
     #[error("Unable to uninstall system version")]
     CantUninstallSystemVersion,
-    #[error("Too many versions had matched, please be more specific.\nFound {} matching versions, expected 1:\n{}", matched_versions.len(), matched_versions.iter().map(|v| format!("* {v}")).collect::<Vec<_>>().join("\n"))]
+    #[error("Too many versions had matched, please be more specific or use --all-matching.\nFound {} matching versions, expected 1:\n{}", matched_versions.len(), matched_versions.iter().map(|v| format!("* {v}")).collect::<Vec<_>>().join("\n"))]
     PleaseBeMoreSpecificToDelete { matched_versions: Vec<String> },
-    #[error("Unable to find a matching version")]
-    CantFindVersion,
+    #[error("Unable to find an installed version matching {}", version)]
+    CantFindVersion { version: UserVersion },
+    #[error("Refusing to uninstall multiple versions without confirmation. Pass --yes to skip the prompt.")]
+    ConfirmationRequired,
     #[error("Root path not found for version {}", version)]
     RootPathNotFound { version: Version },
     #[error("io error: {}", source)]
@@ -1,5 +1,5 @@
 use crate::app_config::AppConfig;
-use crate::symlinked::{remove_symlink_dir, shallow_read_symlink, symlink_dir};
+use crate::symlinked::{replace_symlink_dir, shallow_read_symlink};
 use crate::machine_semver;
 use crate::version::Version;
 use std::convert::TryInto;
@@ -10,16 +10,32 @@ pub fn create_alias(
     common_name: &str,
     version: &Version,
 ) -> std::io::Result<()> {
+    let _lock = crate::file_lock::lock_aliases(config)?;
+    link_alias(config, common_name, version)
+}
+
+/// Tags `version` as the default version, unless there is one already.
+/// Returns whether it was tagged.
+pub fn create_default_alias_if_missing(
+    config: &AppConfig,
+    version: &Version,
+) -> std::io::Result<bool> {
+    let _lock = crate::file_lock::lock_aliases(config)?;
+    if config.version_std_dir().exists() {
+        return Ok(false);
+    }
+    link_alias(config, "default", version)?;
+    Ok(true)
+}
+
+fn link_alias(config: &AppConfig, common_name: &str, version: &Version) -> std::io::Result<()> {
     let aliases_dir = config.aliases_dir();
     std::fs::create_dir_all(&aliases_dir)?;
 
     let version_dir = version.installation_path(config);
     let alias_dir = aliases_dir.join(common_name);
 
-    remove_symlink_dir(&alias_dir).ok();
-    symlink_dir(version_dir, &alias_dir)?;
-
-    Ok(())
+    replace_symlink_dir(version_dir, alias_dir)
 }
 
 pub fn list_aliases(config: &AppConfig) -> std::io::Result<Vec<StoredAlias>> {
@@ -44,7 +60,8 @@ impl std::convert::TryInto<StoredAlias> for &std::path::Path {
         let destination_path = if shallow_self == machine_semver::path() {
             shallow_self
         } else {
-            std::fs::canonicalize(&shallow_self)?
+            // An alias of a linked version must not resolve into the linked prefix
+            crate::linked::resolve_installation(self)?
         };
         Ok(StoredAlias {
             alias_path: PathBuf::from(self),
@@ -0,0 +1,179 @@
+//! A version can be installed for several CPU architectures side by side:
+//!
+//! ```text
+//! node-versions/v20.11.1/
+//!   installation -> arm64/installation
+//!   arm64/installation/
+//!   arm64/install.json
+//!   x64/installation/
+//!   x64/install.json
+//! ```
+//!
+//! `installation` links to the architecture installed first, so the aliases and
+//! shims pointing at it keep working. Versions installed before had their
+//! `installation` directory right in the version directory. They are read as
+//! installs for the native architecture of this machine, and moved into this
+//! layout when another architecture of the version is installed.
+
+use crate::alias::StoredAlias;
+use crate::app_config::AppConfig;
+use crate::cpu_arch::{get_safe_cpu_arch, CPUArch};
+use crate::symlinked::replace_symlink_dir;
+use crate::version::Version;
+use log::debug;
+use std::convert::TryInto;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The directory of a version's installation for `cpu_arch`
+pub fn arch_dir(config: &AppConfig, version: &Version, cpu_arch: CPUArch) -> PathBuf {
+    config
+        .installations_dir()
+        .join(version.v_str())
+        .join(cpu_arch.as_str())
+}
+
+/// The architectures a version is installed for, with the first installed one first
+pub fn installed_archs(config: &AppConfig, version: &Version) -> Vec<CPUArch> {
+    let version_dir = config.installations_dir().join(version.v_str());
+    if is_legacy(&version_dir) {
+        return vec![legacy_arch(version)];
+    }
+    let mut archs: Vec<CPUArch> = std::fs::read_dir(version_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("installation").exists())
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    let primary = primary_arch(config, version);
+    archs.sort_by_key(|cpu_arch| (Some(*cpu_arch) != primary, cpu_arch.as_str()));
+    archs
+}
+
+/// The architecture the `installation` link of a version points to
+pub fn primary_arch(config: &AppConfig, version: &Version) -> Option<CPUArch> {
+    if is_legacy(&config.installations_dir().join(version.v_str())) {
+        return Some(legacy_arch(version));
+    }
+    let target = std::fs::read_link(version.installation_path(config)).ok()?;
+    target.parent()?.file_name()?.to_str()?.parse().ok()
+}
+
+/// Points the `installation` link of a version to its install for `cpu_arch`
+pub fn set_primary(
+    config: &AppConfig,
+    version: &Version,
+    cpu_arch: CPUArch,
+) -> std::io::Result<()> {
+    replace_symlink_dir(
+        arch_dir(config, version, cpu_arch).join("installation"),
+        version.installation_path(config),
+    )
+}
+
+/// Points the `installation` link of a version to `cpu_arch`, unless it has one already
+pub fn set_primary_if_missing(
+    config: &AppConfig,
+    version: &Version,
+    cpu_arch: CPUArch,
+) -> std::io::Result<()> {
+    if version.installation_path(config).exists() {
+        return Ok(());
+    }
+    set_primary(config, version, cpu_arch)
+}
+
+/// Whether the version in `version_dir` was installed before architectures were recorded
+fn is_legacy(version_dir: &Path) -> bool {
+    std::fs::symlink_metadata(version_dir.join("installation"))
+        .is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// The architecture a version installed before architectures were recorded
+/// runs on. It isn't taken from `--cpu-arch`, which only applies to the
+/// current command.
+fn legacy_arch(version: &Version) -> CPUArch {
+    get_safe_cpu_arch(CPUArch::default(), version)
+}
+
+/// Moves a version installed before architectures were recorded into the current layout.
+/// This must be called with the lock of the version held.
+pub fn migrate(config: &AppConfig, version: &Version) -> std::io::Result<()> {
+    let version_dir = config.installations_dir().join(version.v_str());
+    if !is_legacy(&version_dir) {
+        return Ok(());
+    }
+
+    let legacy_installation = version_dir.join("installation");
+    let cpu_arch = legacy_arch(version);
+    let arch_dir = arch_dir(config, version, cpu_arch);
+    debug!(
+        "Moving {} into {}",
+        legacy_installation.display(),
+        arch_dir.display()
+    );
+    std::fs::create_dir_all(&arch_dir)?;
+    std::fs::rename(&legacy_installation, arch_dir.join("installation"))?;
+    move_if_exists(
+        &version_dir.join("install.json"),
+        &arch_dir.join("install.json"),
+    )?;
+    set_primary(config, version, cpu_arch)
+}
+
+fn move_if_exists(from: &Path, to: &Path) -> std::io::Result<()> {
+    match std::fs::rename(from, to) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// The installation of a version to use for the architecture in `config`.
+/// Without an architecture chosen explicitly, a version that isn't installed
+/// for this machine's architecture falls back to the one installed first.
+pub fn installation_for(config: &AppConfig, version: &Version) -> Result<PathBuf, NotInstalled> {
+    let cpu_arch = get_safe_cpu_arch(config.cpu_arch, version);
+    if primary_arch(config, version) == Some(cpu_arch) {
+        return Ok(version.installation_path(config));
+    }
+
+    let arch_installation = arch_dir(config, version, cpu_arch).join("installation");
+    if arch_installation.exists() {
+        Ok(arch_installation)
+    } else if config.cpu_arch == CPUArch::default() {
+        Ok(version.installation_path(config))
+    } else {
+        Err(NotInstalled {
+            version: version.clone(),
+            cpu_arch,
+        })
+    }
+}
+
+/// Like `installation_for`, for the version an alias points to.
+/// Without an architecture chosen explicitly this is the alias itself,
+/// so a shim pointing to it follows the alias when it changes.
+pub fn alias_installation_for(
+    config: &AppConfig,
+    alias_path: &Path,
+) -> Result<PathBuf, NotInstalled> {
+    if config.cpu_arch == CPUArch::default() {
+        return Ok(alias_path.to_path_buf());
+    }
+
+    let aliased_version = TryInto::<StoredAlias>::try_into(alias_path)
+        .ok()
+        .and_then(|alias| Version::parse(alias.s_ver()).ok());
+    match aliased_version {
+        Some(version @ Version::Semver(_)) => installation_for(config, &version),
+        _ => Ok(alias_path.to_path_buf()),
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{version} is not installed for {cpu_arch}. Install it with `enm install {version} --cpu-arch {cpu_arch}`")]
+pub struct NotInstalled {
+    pub version: Version,
+    pub cpu_arch: CPUArch,
+}
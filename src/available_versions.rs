@@ -1,10 +1,10 @@
+use crate::app_config::AppConfig;
 use crate::version::Version;
-use std::path::Path;
 use thiserror::Error;
 
-pub fn list<P: AsRef<Path>>(installations_dir: P) -> Result<Vec<Version>, Error> {
+pub fn list(config: &AppConfig) -> Result<Vec<Version>, Error> {
     let mut vec = vec![];
-    for result_entry in installations_dir.as_ref().read_dir()? {
+    for result_entry in config.installations_dir().read_dir()? {
         let entry = result_entry?;
         if entry
             .file_name()
@@ -20,7 +20,11 @@ pub fn list<P: AsRef<Path>>(installations_dir: P) -> Result<Vec<Version>, Error>
             .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?
             .to_str()
             .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))?;
-        let version = Version::parse(filename)?;
+        // Only `enm link` puts versions which aren't semver in here
+        let version = match Version::parse(filename)? {
+            Version::Alias(name) => Version::Linked(name),
+            version => version,
+        };
         vec.push(version);
     }
     Ok(vec)
@@ -0,0 +1,110 @@
+use crate::app_config::{is_custom_mirror, AppConfig};
+use crate::cpu_arch::CPUArch;
+use node_semver::Identifier;
+use url::Url;
+
+/// A Node.js release channel. Every channel but `release` is published
+/// under its own directory in the download mirror, like `download/rc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, clap::ValueEnum)]
+pub enum Channel {
+    /// Stable releases
+    #[default]
+    Release,
+    /// Release candidates, like `v23.0.0-rc.1`
+    Rc,
+    /// Nightly builds of the main branch
+    Nightly,
+    /// Builds of pull requests and experiments
+    Test,
+    /// Nightly builds with the latest V8 canary
+    #[clap(name = "v8-canary")]
+    V8Canary,
+}
+
+impl Channel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Release => "release",
+            Self::Rc => "rc",
+            Self::Nightly => "nightly",
+            Self::Test => "test",
+            Self::V8Canary => "v8-canary",
+        }
+    }
+
+    /// Detects the channel a version was published in, based on its prerelease tag.
+    pub fn of_version(version: &node_semver::Version) -> Self {
+        match version.pre_release.first() {
+            Some(Identifier::AlphaNumeric(tag)) if tag == "rc" => Self::Rc,
+            Some(Identifier::AlphaNumeric(tag)) if tag.starts_with("nightly") => Self::Nightly,
+            Some(Identifier::AlphaNumeric(tag)) if tag.starts_with("test") => Self::Test,
+            Some(Identifier::AlphaNumeric(tag)) if tag.starts_with("v8-canary") => Self::V8Canary,
+            _ => Self::Release,
+        }
+    }
+
+    /// The mirrors serving this channel's `index.json` and archives for `cpu_arch`,
+    /// in order of preference. Releases for architectures nodejs.org doesn't
+    /// build for come from the unofficial-builds mirror, unless only the dist
+    /// mirror was set, which is then expected to serve them too, so enm doesn't
+    /// reach out of a private network behind the user's back.
+    pub fn dist_mirrors(self, cpu_arch: CPUArch, config: &AppConfig) -> Vec<Url> {
+        match self {
+            Self::Release
+                if cpu_arch.is_unofficial()
+                    && (is_custom_mirror(&config.node_unofficial_mirror)
+                        || !is_custom_mirror(&config.node_dist_mirror)) =>
+            {
+                vec![config.node_unofficial_mirror.clone()]
+            }
+            Self::Release => config.node_dist_mirrors(),
+            channel => vec![Url::parse(&format!(
+                "{}/{}",
+                config.node_download_mirror.as_str().trim_end_matches('/'),
+                channel.as_str()
+            ))
+            .expect("Unable to build the channel mirror URL")],
+        }
+    }
+}
+
+impl std::str::FromStr for Channel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <Self as clap::ValueEnum>::from_str(s, true)
+    }
+}
+
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel_of(version: &str) -> Channel {
+        Channel::of_version(&node_semver::Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn test_of_version() {
+        assert_eq!(channel_of("20.11.1"), Channel::Release);
+        assert_eq!(channel_of("23.0.0-rc.1"), Channel::Rc);
+        assert_eq!(channel_of("23.0.0-nightly20240901abcdef0"), Channel::Nightly);
+        assert_eq!(channel_of("23.0.0-test20240901abcdef0"), Channel::Test);
+        assert_eq!(
+            channel_of("23.0.0-v8-canary20240901abcdef0"),
+            Channel::V8Canary
+        );
+    }
+
+    #[test]
+    fn test_of_version_with_unknown_tag() {
+        assert_eq!(channel_of("20.0.0-pre"), Channel::Release);
+        assert_eq!(channel_of("20.0.0-rcx.1"), Channel::Release);
+    }
+}
@@ -0,0 +1,128 @@
+//! SHA-256 checksums, in the format of the `SHASUMS256.txt` files Node publishes
+//! next to every release:
+//!
+//! ```text
+//! 2d9a4e8c1e1b6e1e2f3b2e0b7f62f2ad0f3b1d3c6a6e5b4c3d2e1f0a9b8c7d6e  node-v20.11.1-linux-x64.tar.xz
+//! ```
+
+use ring::digest::{Context, SHA256};
+use std::fmt::Write;
+use std::io::Read;
+use std::path::Path;
+
+/// The hex-encoded SHA-256 digest of everything read from `reader`
+pub fn sha256(mut reader: impl Read) -> std::io::Result<String> {
+    let mut context = Context::new(&SHA256);
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        match reader.read(&mut buffer)? {
+            0 => break,
+            read => context.update(&buffer[..read]),
+        }
+    }
+    Ok(context
+        .finish()
+        .as_ref()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            write!(hex, "{byte:02x}").expect("Writing to a string can't fail");
+            hex
+        }))
+}
+
+pub fn sha256_file(path: &Path) -> std::io::Result<String> {
+    sha256(std::fs::File::open(path)?)
+}
+
+/// The entries of a `SHASUMS256.txt` file, in order
+#[derive(Debug, Default)]
+pub struct Shasums(Vec<(String, String)>);
+
+impl Shasums {
+    pub fn parse(text: &str) -> Self {
+        Self(
+            text.lines()
+                .filter_map(|line| {
+                    let (checksum, file_name) = line.split_once(char::is_whitespace)?;
+                    // `sha256sum --binary` marks file names with a `*`
+                    let file_name = file_name.trim_start().trim_start_matches('*');
+                    Some((checksum.to_lowercase(), file_name.to_string()))
+                })
+                .collect(),
+        )
+    }
+
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    pub fn get(&self, file_name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(_, name)| name == file_name)
+            .map(|(checksum, _)| checksum.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(checksum, name)| (checksum.as_str(), name.as_str()))
+    }
+
+    pub fn retain(&mut self, f: impl Fn(&str) -> bool) {
+        self.0.retain(|(_, name)| f(name));
+    }
+}
+
+impl std::fmt::Display for Shasums {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (checksum, file_name) in &self.0 {
+            writeln!(f, "{checksum}  {file_name}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shasums() {
+        let shasums = Shasums::parse(
+            "ABCDEF01  node-v20.11.1-linux-x64.tar.xz\n\
+             0123abcd *node-v20.11.1-win-x64.zip\n\
+             \n\
+             not-a-line\n",
+        );
+
+        assert_eq!(
+            shasums.get("node-v20.11.1-linux-x64.tar.xz"),
+            Some("abcdef01")
+        );
+        assert_eq!(shasums.get("node-v20.11.1-win-x64.zip"), Some("0123abcd"));
+        assert_eq!(shasums.get("not-a-line"), None);
+        assert_eq!(shasums.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_shasums_round_trip() {
+        let text = "abcdef01  node-v20.11.1-linux-x64.tar.xz\n0123abcd  node-v20.11.1-darwin-arm64.tar.gz\n";
+        let mut shasums = Shasums::parse(text);
+        assert_eq!(shasums.to_string(), text);
+
+        shasums.retain(|file_name| file_name.contains("linux"));
+        assert_eq!(
+            shasums.to_string(),
+            "abcdef01  node-v20.11.1-linux-x64.tar.xz\n"
+        );
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256(&b"hello"[..]).unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+}
@@ -10,6 +10,9 @@ pub enum CPUArch {
     Ppc64le,
     Ppc64,
     S390x,
+    Riscv64,
+    Loong64,
+    Armv6l,
 }
 
 impl CPUArch {
@@ -23,8 +26,19 @@ impl CPUArch {
             CPUArch::Ppc64le => "ppc64le",
             CPUArch::Ppc64 => "ppc64",
             CPUArch::S390x => "s390x",
+            CPUArch::Riscv64 => "riscv64",
+            CPUArch::Loong64 => "loong64",
+            CPUArch::Armv6l => "armv6l",
         }
     }
+
+    /// Whether Node is only built for this architecture by the unofficial-builds project
+    pub fn is_unofficial(self) -> bool {
+        matches!(
+            self,
+            CPUArch::X64Musl | CPUArch::Riscv64 | CPUArch::Loong64 | CPUArch::Armv6l
+        )
+    }
 }
 
 #[cfg(unix)]
@@ -47,6 +61,8 @@ pub fn get_safe_cpu_arch(cpu_arch: CPUArch, _version: &Version) -> CPUArch {
 impl Default for CPUArch {
     fn default() -> CPUArch {
         match crate::system_info::platform_cpu_arch().parse() {
+            // glibc builds don't run on musl distributions like Alpine
+            Ok(CPUArch::X64) if crate::system_info::is_musl() => CPUArch::X64Musl,
             Ok(cpu_arch) => cpu_arch,
             Err(e) => panic!("{}", e.details),
         }
@@ -65,6 +81,9 @@ impl std::str::FromStr for CPUArch {
             "ppc64le" => Ok(CPUArch::Ppc64le),
             "ppc64" => Ok(CPUArch::Ppc64),
             "s390x" => Ok(CPUArch::S390x),
+            "riscv64" => Ok(CPUArch::Riscv64),
+            "loong64" => Ok(CPUArch::Loong64),
+            "armv6l" => Ok(CPUArch::Armv6l),
             unknown => Err(CPUArchError::new(format!("Unknown CPUArch: {unknown}"))),
         }
     }
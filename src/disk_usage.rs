@@ -0,0 +1,40 @@
+use std::path::Path;
+
+/// Sums up the size of every file under `path`, without following symlinks.
+pub fn dir_size<P: AsRef<Path>>(path: P) -> std::io::Result<u64> {
+    let metadata = std::fs::symlink_metadata(path.as_ref())?;
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let mut total = 0;
+    for entry in std::fs::read_dir(path.as_ref())? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += dir_size(entry.path())?;
+        } else {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Formats a byte count in binary units, like `41.7 MiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} {}", UNITS[0])
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
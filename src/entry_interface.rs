@@ -5,21 +5,21 @@ use clap::Parser;
 
 #[derive(clap::Parser, Debug)]
 pub enum Evaluator {
-    /// Show all remote NodeJS versions
+    /// Show all remote `NodeJS` versions
     #[clap(name = "show-remote", bin_name = "show-remote", visible_aliases = &["sr"])]
     ShowRemote(actions::ls_remote::ShowRemote),
 
-    /// Show all locally installed NodeJS versions
+    /// Show all locally installed `NodeJS` versions
     #[clap(name = "show-local", bin_name = "show-local", visible_aliases = &["sl"])]
     ShowLocal(actions::ls_local::ShowLocal),
 
-    /// Install a provided NodeJS version
+    /// Install a provided `NodeJS` version
     #[clap(name = "install", bin_name = "install", visible_aliases = &["i"])]
     Install(actions::firstrun::FirstRun),
 
-    /// Switch to provided NodeJS version
+    /// Switch to provided `NodeJS` version
     /// 
-    /// Ex: Switch to latest NodeJS version 18 
+    /// Ex: Switch to latest `NodeJS` version 18 
     /// `enm switch 18`
     #[clap(name = "switch", bin_name = "switch")]
     Switch(actions::switch::Switch),
@@ -30,29 +30,76 @@ pub enum Evaluator {
     #[clap(name = "source", bin_name = "source")]
     AppVan(actions::app_van::AppVan),
 
-    /// Set provided version as the default NodeJS version
+    /// Set provided version as the default `NodeJS` version
     ///
     #[clap(name = "default", bin_name = "default")]
     Default(actions::default::Default),
 
-    /// Print the NodeJS version currenly in use
+    /// Print the `NodeJS` version currenly in use
     #[clap(name = "inuse", bin_name = "inuse")]
     InUse(actions::in_use::InUse),
 
     /// Run commands under a specific node version
     ///
-    /// Ex: Run commands under NodeJS version 18 
+    /// Ex: Run commands under `NodeJS` version 18 
     /// enm run --with=v18 node --version
     /// => v18.20.12
     #[clap(name = "run", bin_name = "run", verbatim_doc_comment)]
     RunX(actions::runx::RunX),
     
-    /// Uninstall provided NodeJS version
+    /// Uninstall provided `NodeJS` versions
     ///
-    /// Ex: Uninstall NodeJS version 16 
+    /// Ex: Uninstall `NodeJS` version 18
     /// enm uninstall 18
-    #[clap(name = "uninstall", bin_name = "uninstall", visible_aliases = &["ui"])]
+    ///
+    /// Ex: Uninstall every installed `NodeJS` 16 version
+    /// enm uninstall 16 --all-matching
+    ///
+    /// Ex: Uninstall everything but the default version
+    /// enm uninstall --all-except default --yes
+    #[clap(name = "uninstall", bin_name = "uninstall", visible_aliases = &["ui"], verbatim_doc_comment)]
     Uninstall(actions::uninstall::Uninstall),
+
+    /// Show newer patch releases for installed `NodeJS` versions and aliases
+    ///
+    /// Exits with code 2 when a newer release in the same major contains security fixes.
+    #[clap(name = "outdated", bin_name = "outdated")]
+    Outdated(actions::outdated::Outdated),
+
+    /// Download `NodeJS` for any platform into a directory, without installing it
+    ///
+    /// Ex: Download `NodeJS` 20 for Linux on ARM, like for a Docker image
+    /// enm fetch 20 --platform linux --cpu-arch arm64 --output ./dist
+    #[clap(name = "fetch", bin_name = "fetch", verbatim_doc_comment)]
+    Fetch(actions::fetch::Fetch),
+
+    /// Install a `NodeJS` archive from a file, like a vendor-patched build
+    ///
+    /// Ex: Import a tarball, verifying its checksum
+    /// enm import ./node-v20.11.1-linux-x64.tar.xz --shasums ./SHASUMS256.txt
+    #[clap(name = "import", bin_name = "import", verbatim_doc_comment)]
+    Import(actions::import::Import),
+
+    /// Use a `NodeJS` prefix built outside of enm under a name, like a custom build
+    ///
+    /// Ex: Link an ASAN build, and run tests with it
+    /// enm link custom-asan ~/src/node/out/asan
+    /// enm run --with=custom-asan npm test
+    ///
+    /// `enm uninstall custom-asan` unregisters it, leaving the prefix untouched.
+    #[clap(name = "link", bin_name = "link", verbatim_doc_comment)]
+    Link(actions::link::Link),
+
+    /// Remove the shims of shells which exited
+    ///
+    /// `enm source` creates a shim for every shell, and removes the ones
+    /// of shells which exited a day after they were last switched.
+    #[clap(name = "clean-shims", bin_name = "clean-shims")]
+    CleanShims(actions::clean_shims::CleanShims),
+
+    /// Manage a local mirror of `NodeJS` releases, for machines without internet access
+    #[clap(name = "mirror", bin_name = "mirror")]
+    Mirror(actions::mirror::Mirror),
 }
 
 impl Evaluator {
@@ -67,11 +114,17 @@ impl Evaluator {
             Self::InUse(cmd) => cmd.call(config),
             Self::RunX(cmd) => cmd.call(config),
             Self::Uninstall(cmd) => cmd.call(config),
+            Self::Outdated(cmd) => cmd.call(config),
+            Self::Fetch(cmd) => cmd.call(config),
+            Self::Import(cmd) => cmd.call(config),
+            Self::Link(cmd) => cmd.call(config),
+            Self::CleanShims(cmd) => cmd.call(config),
+            Self::Mirror(cmd) => cmd.call(config),
         }
     }
 }
 
-/// Easy NodeJS (version) manager (ENM).
+/// Easy `NodeJS` (version) manager (ENM).
 #[derive(clap::Parser, Debug)]
 #[clap(name = "enm", version = env!("CARGO_PKG_VERSION"), bin_name = "enm")]
 pub struct Cli {
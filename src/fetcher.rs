@@ -1,7 +1,10 @@
+use crate::app_config::AppConfig;
 use crate::cpu_arch::CPUArch;
+use crate::install_metadata::InstallMetadata;
 use crate::loaders::ResponseProgress;
 use crate::pressed::{Archive, Error as ExtractError};
 use crate::prtl_folder::DirectoryPortal;
+use crate::system_info::Platform;
 use crate::version::Version;
 use indicatif::ProgressDrawTarget;
 use log::debug;
@@ -31,50 +34,119 @@ pub enum Error {
     EmptyTape,
     #[error("{} for {} not found upstream.\nYou can `enm show-remote` to see available versions or try a different `--cpu_arch`.", version, cpu_arch)]
     VersionNotFound { version: Version, cpu_arch: CPUArch },
+    #[error("{} has no {}-{} build; available: {}", version, platform, cpu_arch, if available.is_empty() { "none".to_string() } else { available.join(", ") })]
+    CPUArchNotPublished {
+        version: Version,
+        platform: &'static str,
+        cpu_arch: CPUArch,
+        available: Vec<String>,
+    },
     #[error("Version already installed at {:?}", path)]
     VersionAlreadyInstalled { path: PathBuf },
+    #[error("The headers of {} were not found upstream", version)]
+    HeadersNotFound { version: Version },
+    #[error("The mirror is unavailable, it responded with {}", status)]
+    MirrorUnavailable { status: reqwest::StatusCode },
+    #[error("The mirror refused the request with {}. Check the credentials it is accessed with", status)]
+    AccessDenied { status: reqwest::StatusCode },
 }
 
-#[cfg(unix)]
-fn filename_for_version(version: &Version, cpu_arch: CPUArch, ext: &str) -> String {
-    format!(
-        "node-{node_ver}-{platform}-{cpu_arch}.{ext}",
-        node_ver = &version,
-        platform = crate::system_info::platform_name(),
-        cpu_arch = cpu_arch,
-        ext = ext
-    )
+impl Error {
+    pub fn is_outage(&self) -> bool {
+        match self {
+            Self::HttpError { source } => source.is_outage(),
+            Self::MirrorUnavailable { .. } => true,
+            _ => false,
+        }
+    }
 }
 
-#[cfg(windows)]
-fn filename_for_version(version: &Version, cpu_arch: CPUArch, ext: &str) -> String {
-    format!(
-        "node-{node_ver}-win-{cpu_arch}.{ext}",
-        node_ver = &version,
-        cpu_arch = cpu_arch,
-        ext = ext,
-    )
+pub fn filename_for_version(
+    version: &Version,
+    platform: Platform,
+    cpu_arch: CPUArch,
+    ext: &str,
+) -> String {
+    format!("node-{version}-{platform}-{cpu_arch}.{ext}")
 }
 
-fn download_url(base_url: &Url, version: &Version, cpu_arch: CPUArch, ext: &str) -> Url {
+/// The platform name used in the `files` key of `index.json`, and the
+/// suffix marking the archive kind we are able to extract.
+fn index_platform(platform: Platform) -> (&'static str, &'static str) {
+    match platform {
+        Platform::Linux => ("linux", ""),
+        Platform::Darwin => ("osx", "-tar"),
+        Platform::Win => ("win", "-zip"),
+    }
+}
+
+/// Picks the archives to download for a version, based on the platforms the
+/// index lists in its `files` key. Mirrors which don't publish `files` get
+/// every supported archive, which are then probed in order.
+pub fn archives_for(
+    version: &Version,
+    published_files: &[String],
+    platform: Platform,
+    cpu_arch: CPUArch,
+) -> Result<Vec<&'static Archive>, Error> {
+    let archives = Archive::for_platform(platform);
+    if published_files.is_empty() {
+        return Ok(archives.iter().collect());
+    }
+
+    let (index_name, suffix) = index_platform(platform);
+    let wanted = format!("{index_name}-{cpu_arch}{suffix}");
+    if published_files.iter().any(|file| *file == wanted) {
+        return Ok(archives
+            .iter()
+            .filter(|archive| archive.is_published_for(version))
+            .collect());
+    }
+
+    let available = published_files
+        .iter()
+        .filter_map(|file| file.strip_prefix(index_name)?.strip_prefix('-'))
+        .filter_map(|file| file.strip_suffix(suffix))
+        .map(String::from)
+        .collect();
+    Err(Error::CPUArchNotPublished {
+        version: version.clone(),
+        platform: index_name,
+        cpu_arch,
+        available,
+    })
+}
+
+fn download_url(
+    base_url: &Url,
+    version: &Version,
+    platform: Platform,
+    cpu_arch: CPUArch,
+    ext: &str,
+) -> Url {
     Url::parse(&format!(
         "{}/{}/{}",
         base_url.as_str().trim_end_matches('/'),
         version,
-        filename_for_version(version, cpu_arch, ext)
+        filename_for_version(version, platform, cpu_arch, ext)
     ))
     .unwrap()
 }
 
-/// Install a Node package
+/// Install a Node package. `published_files` is the `files` key of the
+/// version in `index.json`, used to pick the archive to download.
+/// The mirrors are tried in order, and the one which served the archive is returned.
 pub fn install_node_dist<P: AsRef<Path>>(
     version: &Version,
-    node_dist_mirror: &Url,
+    published_files: &[String],
+    mirrors: &[Url],
     installations_dir: P,
     cpu_arch: CPUArch,
     show_loaders: bool,
-) -> Result<(), Error> {
-    let installation_dir = PathBuf::from(installations_dir.as_ref()).join(version.v_str());
+    config: &AppConfig,
+) -> Result<Url, Error> {
+    crate::arch_installs::migrate(config, version)?;
+    let installation_dir = crate::arch_installs::arch_dir(config, version, cpu_arch);
 
     if installation_dir.exists() {
         return Err(Error::VersionAlreadyInstalled {
@@ -82,45 +154,207 @@ pub fn install_node_dist<P: AsRef<Path>>(
         });
     }
 
-    std::fs::create_dir_all(installations_dir.as_ref())?;
-
     let temp_installations_dir = installations_dir.as_ref().join(".downloads");
     std::fs::create_dir_all(&temp_installations_dir)?;
 
-    let portal = DirectoryPortal::new_in(&temp_installations_dir, installation_dir);
+    let archives = archives_for(version, published_files, Platform::current(), cpu_arch)?;
+    let (portal, mirror) =
+        crate::mirrors::try_in_order(mirrors, config, Error::is_outage, |mirror| {
+            let portal = DirectoryPortal::new_in(&temp_installations_dir, installation_dir.clone());
+            download_from(
+                mirror,
+                version,
+                &archives,
+                &portal,
+                Platform::current(),
+                cpu_arch,
+                show_loaders,
+            )?;
+            Ok(portal)
+        })?;
 
-    for extract in Archive::supported() {
-        let ext = extract.file_extension();
-        let url = download_url(node_dist_mirror, version, cpu_arch, ext);
-        debug!("Going to call for {}", &url);
-        let response = crate::http::get(url.as_str())?;
+    InstallMetadata {
+        mirror: Some(crate::http::redact(&mirror)),
+        ..InstallMetadata::default()
+    }
+    .write(&portal)?;
+    std::fs::create_dir_all(installations_dir.as_ref().join(version.v_str()))?;
+    portal.teleport()?;
+    crate::arch_installs::set_primary_if_missing(config, version, cpu_arch)?;
 
-        if !response.status().is_success() {
-            continue;
-        }
+    Ok(mirror)
+}
 
-        debug!("Extracting response...");
-        if show_loaders {
-            extract.extract_pressed_into(
-                portal.as_ref(),
-                ResponseProgress::new(response, ProgressDrawTarget::stderr()),
-            )?;
+/// Extracts a Node archive into `portal`, renaming its top level directory
+/// to `installation`.
+pub fn extract_installation(
+    extract: &Archive,
+    reader: impl std::io::Read,
+    portal: &Path,
+) -> Result<(), Error> {
+    extract.extract_pressed_into(portal, reader)?;
+
+    let installed_directory = std::fs::read_dir(portal)?
+        .next()
+        .ok_or(Error::EmptyTape)??;
+    let installed_directory = installed_directory.path();
+
+    let renamed_installation_dir = portal.join("installation");
+    std::fs::rename(installed_directory, renamed_installation_dir)?;
+    Ok(())
+}
+
+/// Downloads the headers of a version from `mirror`, and merges them into the
+/// `installation` directory in `version_dir`, the directory of one of its architectures. They are laid out like the
+/// installation itself, so node-gyp finds them with `npm_config_nodedir`.
+/// On Windows, the `node.lib` addons link against is downloaded along.
+pub fn install_headers(
+    version: &Version,
+    mirror: &Url,
+    version_dir: &Path,
+    cpu_arch: CPUArch,
+    show_loaders: bool,
+) -> Result<(), Error> {
+    let response = get_headers_file(
+        version,
+        mirror,
+        &format!("node-{version}-headers.tar.gz"),
+    )?;
+
+    let temp_installations_dir = version_dir
+        .ancestors()
+        .nth(2)
+        .expect("An architecture is in the installations directory")
+        .join(".downloads");
+    std::fs::create_dir_all(&temp_installations_dir)?;
+    let temp_dir = tempfile::TempDir::new_in(&temp_installations_dir)?;
+
+    // Headers are gzipped on every platform, like the source tarballs
+    let extract = Archive::source();
+    if show_loaders {
+        extract.extract_pressed_into(
+            temp_dir.path(),
+            ResponseProgress::new(response, ProgressDrawTarget::stderr()),
+        )?;
+    } else {
+        extract.extract_pressed_into(temp_dir.path(), response)?;
+    }
+
+    let headers_dir = std::fs::read_dir(temp_dir.path())?
+        .next()
+        .ok_or(Error::EmptyTape)??;
+    merge_into(&headers_dir.path(), &version_dir.join("installation"))?;
+
+    if Platform::current() == Platform::Win {
+        install_node_lib(version, mirror, version_dir, cpu_arch)?;
+    }
+    Ok(())
+}
+
+/// Downloads `node.lib` where node-gyp looks for it when `nodedir` is set,
+/// which is the `Release` directory of a Node build tree.
+fn install_node_lib(
+    version: &Version,
+    mirror: &Url,
+    version_dir: &Path,
+    cpu_arch: CPUArch,
+) -> Result<(), Error> {
+    let mut response = get_headers_file(version, mirror, &format!("win-{cpu_arch}/node.lib"))?;
+    let release_dir = version_dir.join("installation").join("Release");
+    std::fs::create_dir_all(&release_dir)?;
+    let mut file = std::fs::File::create(release_dir.join("node.lib"))?;
+    std::io::copy(&mut response, &mut file)?;
+    Ok(())
+}
+
+/// Requests a file of the headers of a version, like `node-v20.11.1-headers.tar.gz`
+fn get_headers_file(
+    version: &Version,
+    mirror: &Url,
+    path: &str,
+) -> Result<crate::http::Response, Error> {
+    let url = Url::parse(&format!(
+        "{}/{}/{}",
+        mirror.as_str().trim_end_matches('/'),
+        version,
+        path
+    ))
+    .unwrap();
+    debug!("Going to call for {}", crate::http::redact(&url));
+    let response = crate::http::get(url.as_str())?;
+
+    if response.status().is_server_error() {
+        return Err(Error::MirrorUnavailable {
+            status: response.status(),
+        });
+    }
+    if is_access_denied(response.status()) {
+        return Err(Error::AccessDenied {
+            status: response.status(),
+        });
+    }
+    if !response.status().is_success() {
+        return Err(Error::HeadersNotFound {
+            version: version.clone(),
+        });
+    }
+    Ok(response)
+}
+
+/// Moves everything in `from` into `to`, replacing the files which exist in both.
+fn merge_into(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            merge_into(&entry.path(), &target)?;
         } else {
-            extract.extract_pressed_into(portal.as_ref(), response)?;
+            std::fs::rename(entry.path(), target)?;
         }
-        debug!("Extraction completed");
+    }
+    Ok(())
+}
 
-        let installed_directory = std::fs::read_dir(&portal)?
-            .next()
-            .ok_or(Error::EmptyTape)??;
-        let installed_directory = installed_directory.path();
+/// Whether the mirror refused the credentials of the request, rather than
+/// not having the requested file
+fn is_access_denied(status: reqwest::StatusCode) -> bool {
+    matches!(
+        status,
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+    )
+}
 
-        let renamed_installation_dir = portal.join("installation");
-        std::fs::rename(installed_directory, renamed_installation_dir)?;
+/// Requests the first archive of a version the mirror has, returning the
+/// response along with the kind of archive it is.
+pub fn fetch_archive<'a>(
+    mirror: &Url,
+    version: &Version,
+    archives: &[&'a Archive],
+    platform: Platform,
+    cpu_arch: CPUArch,
+) -> Result<(crate::http::Response, &'a Archive), Error> {
+    for extract in archives {
+        let ext = extract.file_extension();
+        let url = download_url(mirror, version, platform, cpu_arch, ext);
+        debug!("Going to call for {}", crate::http::redact(&url));
+        let response = crate::http::get(url.as_str())?;
+
+        if response.status().is_server_error() {
+            return Err(Error::MirrorUnavailable {
+                status: response.status(),
+            });
+        }
 
-        portal.teleport()?;
+        if is_access_denied(response.status()) {
+            return Err(Error::AccessDenied {
+                status: response.status(),
+            });
+        }
 
-        return Ok(());
+        if response.status().is_success() {
+            return Ok((response, extract));
+        }
     }
 
     Err(Error::VersionNotFound {
@@ -128,3 +362,89 @@ pub fn install_node_dist<P: AsRef<Path>>(
         cpu_arch,
     })
 }
+
+/// Downloads the first archive of a version the mirror has, and extracts it
+/// into `portal` as the `installation` directory.
+pub fn download_from(
+    mirror: &Url,
+    version: &Version,
+    archives: &[&Archive],
+    portal: &Path,
+    platform: Platform,
+    cpu_arch: CPUArch,
+    show_loaders: bool,
+) -> Result<(), Error> {
+    let (response, extract) = fetch_archive(mirror, version, archives, platform, cpu_arch)?;
+
+    debug!("Extracting response...");
+    if show_loaders {
+        extract_installation(
+            extract,
+            ResponseProgress::new(response, ProgressDrawTarget::stderr()),
+            portal,
+        )?;
+    } else {
+        extract_installation(extract, response, portal)?;
+    }
+    debug!("Extraction completed");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(files: &[&str]) -> Vec<String> {
+        files.iter().map(ToString::to_string).collect()
+    }
+
+    fn extensions(archives: &[&Archive]) -> Vec<&'static str> {
+        archives.iter().map(|archive| archive.file_extension()).collect()
+    }
+
+    #[test]
+    fn test_archives_for_published_platform() {
+        let version = Version::parse("v20.11.1").unwrap();
+        let published = files(&["linux-x64", "osx-arm64-tar", "win-x64-zip"]);
+
+        let archives = archives_for(&version, &published, Platform::Linux, CPUArch::X64).unwrap();
+        assert_eq!(extensions(&archives), ["tar.xz", "tar.gz"]);
+
+        let archives =
+            archives_for(&version, &published, Platform::Darwin, CPUArch::Arm64).unwrap();
+        assert_eq!(extensions(&archives), ["tar.xz", "tar.gz"]);
+
+        let archives = archives_for(&version, &published, Platform::Win, CPUArch::X64).unwrap();
+        assert_eq!(extensions(&archives), ["zip"]);
+    }
+
+    #[test]
+    fn test_archives_for_old_versions_skip_xz() {
+        let version = Version::parse("v0.12.18").unwrap();
+        let archives =
+            archives_for(&version, &files(&["linux-x64"]), Platform::Linux, CPUArch::X64)
+                .unwrap();
+        assert_eq!(extensions(&archives), ["tar.gz"]);
+    }
+
+    #[test]
+    fn test_archives_for_without_published_files() {
+        let version = Version::parse("v0.12.18").unwrap();
+        let archives = archives_for(&version, &[], Platform::Linux, CPUArch::X64).unwrap();
+        assert_eq!(extensions(&archives), ["tar.xz", "tar.gz"]);
+    }
+
+    #[test]
+    fn test_archives_for_unpublished_cpu_arch() {
+        let version = Version::parse("v20.11.1").unwrap();
+        let published = files(&["linux-x64", "linux-arm64", "osx-arm64-tar", "src"]);
+
+        let Err(Error::CPUArchNotPublished { available, .. }) =
+            archives_for(&version, &published, Platform::Linux, CPUArch::Ppc64)
+        else {
+            panic!("Expected ppc64 not to be published");
+        };
+        assert_eq!(available, ["x64", "arm64"]);
+    }
+}
@@ -0,0 +1,133 @@
+//! Advisory locks shared by every enm process using the same base directory,
+//! so two installs of a version, or an install and an uninstall, don't race.
+//!
+//! Locks are always taken in the same order, the version first and then the
+//! aliases, so two processes never wait on each other.
+
+use crate::app_config::AppConfig;
+use crate::outln;
+use crate::version::Version;
+use colored::Colorize;
+use std::fs::File;
+use std::path::Path;
+
+/// An exclusive lock, released when dropped or when the process exits.
+#[derive(Debug)]
+pub struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Locks `path`, waiting for the process holding it to release it first.
+    /// `what` describes what is locked, for the message printed while waiting.
+    pub fn acquire(path: &Path, what: &str, config: &AppConfig) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if let Some(file) = imp::try_lock(path)? {
+            return Ok(Self { _file: file });
+        }
+        outln!(
+            config,
+            Info,
+            "Waiting for another enm process to finish with {}",
+            what.cyan()
+        );
+        Ok(Self {
+            _file: imp::lock(path)?,
+        })
+    }
+}
+
+/// Locks the installation of a version, for every architecture
+pub fn lock_version(config: &AppConfig, version: &Version) -> std::io::Result<FileLock> {
+    let path = locks_dir(config).join(format!("{}.lock", version.v_str()));
+    FileLock::acquire(&path, &format!("Node {version}"), config)
+}
+
+/// Locks the aliases, including `default`
+pub fn lock_aliases(config: &AppConfig) -> std::io::Result<FileLock> {
+    let path = locks_dir(config).join("aliases.lock");
+    FileLock::acquire(&path, "the aliases", config)
+}
+
+fn locks_dir(config: &AppConfig) -> std::path::PathBuf {
+    config.base_dir_with_default().join(".locks")
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::fs::{File, OpenOptions};
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    fn open(path: &Path) -> std::io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+    }
+
+    fn flock(file: &File, operation: libc::c_int) -> std::io::Result<()> {
+        loop {
+            // SAFETY: the descriptor is owned by `file`, which outlives the call
+            if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+                return Ok(());
+            }
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    pub fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+        let file = open(path)?;
+        match flock(&file, libc::LOCK_EX | libc::LOCK_NB) {
+            Ok(()) => Ok(Some(file)),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn lock(path: &Path) -> std::io::Result<File> {
+        let file = open(path)?;
+        flock(&file, libc::LOCK_EX)?;
+        Ok(file)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::fs::{File, OpenOptions};
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::path::Path;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+
+    /// Opening the file without sharing it keeps other processes from opening it
+    pub fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+        let opened = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .share_mode(0)
+            .open(path);
+        match opened {
+            Ok(file) => Ok(Some(file)),
+            Err(err) if err.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn lock(path: &Path) -> std::io::Result<File> {
+        loop {
+            if let Some(file) = try_lock(path)? {
+                return Ok(file);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! Details about how a version was installed, kept in `install.json`
+//! next to its `installation` directory.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const FILE_NAME: &str = "install.json";
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct InstallMetadata {
+    /// The mirror the archive was downloaded from, without its password
+    #[serde(default)]
+    pub mirror: Option<String>,
+    /// Whether the headers were merged into the installation, with `--with-headers`
+    #[serde(default)]
+    pub headers: bool,
+}
+
+impl InstallMetadata {
+    /// Reads the metadata of a version, which is empty for versions
+    /// installed before `install.json` existed.
+    pub fn read(version_dir: &Path) -> Self {
+        std::fs::read_to_string(version_dir.join(FILE_NAME))
+            .ok()
+            .and_then(|body| serde_json::from_str(&body).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn write(&self, version_dir: &Path) -> std::io::Result<()> {
+        let body =
+            serde_json::to_string_pretty(self).expect("Can't serialize the install metadata");
+        std::fs::write(version_dir.join(FILE_NAME), body)
+    }
+}
@@ -0,0 +1,70 @@
+//! Node prefixes built outside of enm, like a custom ASAN build, can be
+//! registered under a name with `enm link`. They live next to the installed
+//! versions as `node-versions/<name>/installation`, which is a symlink to the
+//! prefix, so everything that knows where a version is installed finds them too.
+//!
+//! enm never owns a linked prefix: unregistering it only removes the symlink.
+
+use crate::app_config::AppConfig;
+use crate::symlinked::{remove_symlink_dir, symlink_dir};
+use std::path::{Path, PathBuf};
+
+fn installation_path(config: &AppConfig, name: &str) -> PathBuf {
+    config.installations_dir().join(name).join("installation")
+}
+
+/// Whether `name` is registered as a linked version
+pub fn is_linked(config: &AppConfig, name: &str) -> bool {
+    std::fs::symlink_metadata(installation_path(config, name))
+        .is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+/// The prefix a linked version points to
+pub fn target(config: &AppConfig, name: &str) -> std::io::Result<PathBuf> {
+    std::fs::read_link(installation_path(config, name))
+}
+
+pub fn register(config: &AppConfig, name: &str, prefix: &Path) -> std::io::Result<()> {
+    let version_dir = config.installations_dir().join(name);
+    std::fs::create_dir_all(&version_dir)?;
+    symlink_dir(prefix, version_dir.join("installation"))
+}
+
+pub fn unregister(config: &AppConfig, name: &str) -> std::io::Result<()> {
+    remove_symlink_dir(installation_path(config, name))?;
+    // Not `remove_dir_all`, so anything unexpected in there is left alone
+    std::fs::remove_dir(config.installations_dir().join(name))
+}
+
+/// Follows the symlinks from `path`, like an alias or the `plural_ctx`, to the
+/// `installation` directory of a version. Unlike canonicalizing, this stops
+/// before following a linked version out of the installations directory.
+pub fn resolve_installation(path: &Path) -> std::io::Result<PathBuf> {
+    // Like the limit of the OS, so a symlink loop ends
+    const MAX_HOPS: usize = 40;
+
+    let mut path = path.to_path_buf();
+    let mut hops = 0;
+    while path.file_name().map_or(true, |name| name != "installation") {
+        hops += 1;
+        if hops > MAX_HOPS {
+            return Err(std::io::Error::other("Too many levels of symbolic links"));
+        }
+        let target = std::fs::read_link(&path)?;
+        path = match path.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+    }
+
+    let parent = path
+        .parent()
+        .expect("An installation is in a version directory")
+        .canonicalize()?;
+    let installation = parent.join("installation");
+    if installation.exists() {
+        Ok(installation)
+    } else {
+        Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+    }
+}
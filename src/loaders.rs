@@ -1,7 +1,7 @@
 use std::io::Read;
 
+use crate::http::Response;
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
-use reqwest::blocking::Response;
 
 pub struct ResponseProgress {
     loaders: Option<ProgressBar>,
@@ -21,9 +21,7 @@ impl ProgressConfig {
         match self {
             Self::Never => false,
             Self::Always => true,
-            Self::Auto => config
-                .ll_int()
-                .is_writable(crate::ll_int::LLInt::Info),
+            Self::Auto => config.ll_int().is_writable(crate::ll_int::LLInt::Info),
         }
     }
 }
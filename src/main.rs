@@ -8,22 +8,36 @@
 
 mod actions;
 mod alias;
+mod arch_installs;
 mod available_versions;
+mod channel;
+mod checksums;
 mod entry_interface;
 mod app_config;
 mod cpu_arch;
+mod disk_usage;
 mod fetcher;
+mod file_lock;
 mod symlinked;
 mod http;
+mod install_metadata;
+mod linked;
 mod loaders;
 mod long_term_usage;
+mod mirrors;
+mod netrc;
+mod node_headers;
 mod ni_remote;
 mod path_ext;
 mod pressed;
 mod prtl_folder;
 mod reader_uv;
+mod release_schedule;
+mod resolution_cache;
 mod terminators_entry;
+mod shims;
 mod sift_method;
+mod source_build;
 mod system_info;
 mod machine_semver;
 mod user_version;
@@ -31,6 +45,7 @@ mod user_version_in;
 mod version;
 mod version_files;
 mod version_now;
+mod version_record;
 
 #[macro_use]
 mod std_system_structure;
@@ -40,5 +55,6 @@ mod version_std;
 fn main() {
     env_logger::init();
     let value = crate::entry_interface::parse();
+    crate::http::configure(&value.app_cfg);
     value.valuator.call(value.app_cfg);
 }
@@ -0,0 +1,147 @@
+//! A reader for `.netrc` files, which store credentials per host:
+//!
+//! ```text
+//! machine artifactory.example.com
+//!   login deploy
+//!   password s3cr3t
+//! ```
+
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub login: String,
+    pub password: Option<String>,
+}
+
+#[derive(Default)]
+struct Entry {
+    machine: Option<String>,
+    login: Option<String>,
+    password: Option<String>,
+}
+
+impl Entry {
+    fn into_credentials(self) -> Option<Credentials> {
+        Some(Credentials {
+            login: self.login?,
+            password: self.password,
+        })
+    }
+}
+
+/// Finds the credentials of `host` in the netrc file at `path`,
+/// falling back to its `default` entry.
+/// A missing or unreadable file has no credentials.
+pub fn lookup(path: &Path, host: &str) -> Option<Credentials> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    find(&contents, host)
+}
+
+fn find(contents: &str, host: &str) -> Option<Credentials> {
+    let mut entries: Vec<Entry> = vec![];
+    let mut in_macro = false;
+
+    for line in contents.lines() {
+        // Macro definitions run until the next empty line
+        if in_macro {
+            in_macro = !line.trim().is_empty();
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token {
+                "machine" => entries.push(Entry {
+                    machine: tokens.next().map(String::from),
+                    ..Entry::default()
+                }),
+                "default" => entries.push(Entry::default()),
+                "login" => {
+                    if let (Some(entry), Some(value)) = (entries.last_mut(), tokens.next()) {
+                        entry.login = Some(value.to_string());
+                    }
+                }
+                "password" => {
+                    if let (Some(entry), Some(value)) = (entries.last_mut(), tokens.next()) {
+                        entry.password = Some(value.to_string());
+                    }
+                }
+                "account" => {
+                    tokens.next();
+                }
+                "macdef" => {
+                    in_macro = true;
+                    break;
+                }
+                _ if token.starts_with('#') => break,
+                _ => {}
+            }
+        }
+    }
+
+    let (machines, defaults): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .partition(|entry| entry.machine.is_some());
+
+    machines
+        .into_iter()
+        .find(|entry| entry.machine.as_deref() == Some(host))
+        .or_else(|| defaults.into_iter().next())
+        .and_then(Entry::into_credentials)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials(login: &str, password: Option<&str>) -> Credentials {
+        Credentials {
+            login: login.to_string(),
+            password: password.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_find_machine() {
+        let contents = "machine other.example.com login other password nope\n\
+                        machine artifactory.example.com\n  login deploy\n  password s3cr3t\n";
+        assert_eq!(
+            find(contents, "artifactory.example.com"),
+            Some(credentials("deploy", Some("s3cr3t")))
+        );
+        assert_eq!(find(contents, "unknown.example.com"), None);
+    }
+
+    #[test]
+    fn test_find_falls_back_to_default() {
+        let contents = "default login anonymous\nmachine other.example.com login other\n";
+        assert_eq!(
+            find(contents, "artifactory.example.com"),
+            Some(credentials("anonymous", None))
+        );
+        assert_eq!(
+            find(contents, "other.example.com"),
+            Some(credentials("other", None))
+        );
+    }
+
+    #[test]
+    fn test_find_skips_comments_accounts_and_macros() {
+        let contents = "# machine artifactory.example.com login commented\n\
+                        macdef init\nmachine artifactory.example.com login in-macro\n\n\
+                        machine artifactory.example.com account acme login deploy password s3cr3t\n";
+        assert_eq!(
+            find(contents, "artifactory.example.com"),
+            Some(credentials("deploy", Some("s3cr3t")))
+        );
+    }
+
+    #[test]
+    fn test_find_without_login() {
+        assert_eq!(
+            find("machine artifactory.example.com password s3cr3t", "artifactory.example.com"),
+            None
+        );
+    }
+}
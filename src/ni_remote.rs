@@ -1,3 +1,4 @@
+use crate::app_config::AppConfig;
 use crate::version::Version;
 use serde::Deserialize;
 use url::Url;
@@ -34,6 +35,23 @@ pub struct IndexedNodeVersion {
     pub version: Version,
     #[serde(with = "lts_status")]
     pub lts: Option<String>,
+    #[serde(default)]
+    pub date: Option<chrono::NaiveDate>,
+    /// The platforms this version was published for, like `linux-x64` or `win-x64-zip`
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub npm: Option<String>,
+    #[serde(default)]
+    pub v8: Option<String>,
+    #[serde(default)]
+    pub openssl: Option<String>,
+    /// The `NODE_MODULE_VERSION` ABI number
+    #[serde(default)]
+    pub modules: Option<String>,
+    /// Whether this release contains security fixes
+    #[serde(default)]
+    pub security: bool,
 }
 
 /// Prints
@@ -41,10 +59,20 @@ pub struct IndexedNodeVersion {
 /// ```rust
 /// use crate::ni_remote::list;
 /// ```
-pub fn list(base_url: &Url) -> Result<Vec<IndexedNodeVersion>, crate::http::Error> {
-    let index_json_url = format!("{base_url}/index.json");
-    let resp = crate::http::get(&index_json_url)?;
-    let mut value: Vec<IndexedNodeVersion> = resp.json()?;
+pub fn list(
+    mirrors: &[Url],
+    config: &AppConfig,
+) -> Result<Vec<IndexedNodeVersion>, crate::http::Error> {
+    let (mut value, _) = crate::mirrors::try_in_order(
+        mirrors,
+        config,
+        crate::http::Error::is_outage,
+        |base_url| -> Result<Vec<IndexedNodeVersion>, crate::http::Error> {
+            let index_json_url = format!("{}/index.json", base_url.as_str().trim_end_matches('/'));
+            let resp = crate::http::get(&index_json_url)?.error_for_status()?;
+            resp.json()
+        },
+    )?;
     value.sort_by(|a, b| a.version.cmp(&b.version));
     Ok(value)
 }
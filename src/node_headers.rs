@@ -0,0 +1,49 @@
+//! node-gyp builds native addons against the headers of the running Node.
+//! Installations which carry them point npm at themselves with `nodedir` in
+//! their global `etc/npmrc`, so `npm install` in a shell switched to them
+//! doesn't download the headers again.
+//!
+//! The global npmrc has the lowest precedence, so a `nodedir` the user set in
+//! the environment or in `~/.npmrc` still wins.
+
+use log::debug;
+use std::path::Path;
+
+/// Whether an installation has the headers node-gyp needs
+pub fn has_headers(installation: &Path) -> bool {
+    installation
+        .join("include")
+        .join("node")
+        .join("common.gypi")
+        .exists()
+}
+
+/// Sets `nodedir` in the global npmrc of an installation which has its headers,
+/// keeping the rest of the file.
+pub fn expose(installation: &Path) -> std::io::Result<()> {
+    if !has_headers(installation) {
+        return Ok(());
+    }
+
+    let npmrc_path = installation.join("etc").join("npmrc");
+    let npmrc = match std::fs::read_to_string(&npmrc_path) {
+        Ok(npmrc) => npmrc,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+    let has_nodedir = npmrc
+        .lines()
+        .any(|line| line.trim_start().starts_with("nodedir"));
+    if has_nodedir {
+        debug!("{} already sets nodedir", npmrc_path.display());
+        return Ok(());
+    }
+
+    let mut npmrc = npmrc;
+    if !npmrc.is_empty() && !npmrc.ends_with('\n') {
+        npmrc.push('\n');
+    }
+    npmrc.push_str(&format!("nodedir={}\n", installation.display()));
+    std::fs::create_dir_all(installation.join("etc"))?;
+    std::fs::write(&npmrc_path, npmrc)
+}
@@ -5,30 +5,26 @@ pub mod zip;
 use std::io::Read;
 use std::path::Path;
 
+use crate::system_info::Platform;
+use crate::version::Version;
+
 pub use self::all::{Error, Extract};
-#[cfg(unix)]
 use self::tape::Tape;
-
-#[cfg(windows)]
 use self::zip::Zip;
 
+/// Every archive can be extracted on every platform,
+/// so archives for other platforms can be fetched too.
 pub enum Archive {
-    #[cfg(windows)]
     Zip,
-    #[cfg(unix)]
     XzTape,
-    #[cfg(unix)]
     GzTape,
 }
 
 impl Archive {
     pub fn extract_pressed_into(&self, path: &Path, response: impl Read) -> Result<(), Error> {
         let extractor: Box<dyn Extract> = match self {
-            #[cfg(windows)]
             Self::Zip => Box::new(Zip::new(response)),
-            #[cfg(unix)]
             Self::XzTape => Box::new(Tape::Xz(response)),
-            #[cfg(unix)]
             Self::GzTape => Box::new(Tape::Gz(response)),
         };
         extractor.extract_into(path)?;
@@ -37,22 +33,44 @@ impl Archive {
 
     pub fn file_extension(&self) -> &'static str {
         match self {
-            #[cfg(windows)]
             Self::Zip => "zip",
-            #[cfg(unix)]
             Self::XzTape => "tar.xz",
-            #[cfg(unix)]
             Self::GzTape => "tar.gz",
         }
     }
 
-    #[cfg(windows)]
-    pub fn supported() -> &'static [Self] {
-        &[Self::Zip]
+    /// Whether the upstream dist publishes this archive for a version.
+    /// `.tar.xz` archives are only published since Node 4.
+    pub fn is_published_for(&self, version: &Version) -> bool {
+        match self {
+            Self::XzTape => !matches!(version, Version::Semver(v) if v.major < 4),
+            Self::Zip | Self::GzTape => true,
+        }
+    }
+
+    /// The archive format of a file, detected by its extension
+    pub fn for_file_name(file_name: &str) -> Option<&'static Self> {
+        Self::supported()
+            .iter()
+            .chain(std::iter::once(Self::source()))
+            .find(|archive| file_name.ends_with(&format!(".{}", archive.file_extension())))
+    }
+
+    /// The archive format of source tarballs
+    pub fn source() -> &'static Self {
+        &Self::GzTape
     }
 
-    #[cfg(unix)]
+    /// The archives of this platform, in order of preference
     pub fn supported() -> &'static [Self] {
-        &[Self::XzTape, Self::GzTape]
+        Self::for_platform(Platform::current())
+    }
+
+    /// The archives Node publishes for a platform, in order of preference
+    pub fn for_platform(platform: Platform) -> &'static [Self] {
+        match platform {
+            Platform::Win => &[Self::Zip],
+            Platform::Linux | Platform::Darwin => &[Self::XzTape, Self::GzTape],
+        }
     }
 }
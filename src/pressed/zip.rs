@@ -11,7 +11,6 @@ pub struct Zip<R: Read> {
 }
 
 impl<R: Read> Zip<R> {
-    #[allow(dead_code)]
     pub fn new(response: R) -> Self {
         Self { response }
     }
@@ -0,0 +1,159 @@
+//! The Node.js release schedule, used to warn about versions past their end-of-life.
+//! The schedule is fetched from `release_schedule_url` by the commands which go to
+//! the network anyway, like `enm install`, and cached for a week. A failed fetch is
+//! retried a day later, so machines without internet access don't wait on it every
+//! time. Other commands only read the cached copy, or a copy bundled in the binary.
+
+use crate::app_config::AppConfig;
+use crate::outln;
+use crate::version::Version;
+use chrono::NaiveDate;
+use colored::Colorize;
+use log::debug;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+const BUNDLED_SCHEDULE: &str = include_str!("./release_schedule.json");
+const CACHE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const RETRY_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Deserialize, Debug)]
+pub struct ReleaseLine {
+    pub end: NaiveDate,
+}
+
+/// Release lines keyed by their name in the schedule, like `v16` or `v0.12`
+#[derive(Deserialize, Debug)]
+pub struct Schedule(HashMap<String, ReleaseLine>);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EolStatus {
+    Supported { end: NaiveDate },
+    EndingSoon { end: NaiveDate },
+    EndOfLife { end: NaiveDate },
+}
+
+impl EolStatus {
+    pub fn end(self) -> NaiveDate {
+        match self {
+            Self::Supported { end } | Self::EndingSoon { end } | Self::EndOfLife { end } => end,
+        }
+    }
+}
+
+impl Schedule {
+    /// Loads the schedule, refreshing the cached copy when it is stale.
+    pub fn load(config: &AppConfig) -> Self {
+        let cache_path = cache_path(config);
+        let attempt_path = attempt_path(config);
+        let should_fetch = !modified_within(&cache_path, CACHE_MAX_AGE)
+            && !modified_within(&attempt_path, RETRY_AFTER);
+
+        if should_fetch {
+            let fetched = match fetch(config) {
+                Ok(body) if serde_json::from_str::<Schedule>(&body).is_ok() => {
+                    std::fs::write(&cache_path, &body)
+                        .map_err(|err| debug!("Unable to cache the release schedule: {}", err))
+                        .is_ok()
+                }
+                Ok(_) => {
+                    debug!("The fetched release schedule is invalid");
+                    false
+                }
+                Err(err) => {
+                    debug!("Unable to fetch the release schedule: {}", err);
+                    false
+                }
+            };
+            if fetched {
+                std::fs::remove_file(&attempt_path).ok();
+            } else if let Err(err) = std::fs::write(&attempt_path, "") {
+                debug!("Unable to record the failed fetch: {}", err);
+            }
+        }
+
+        Self::load_offline(config)
+    }
+
+    /// Loads the cached schedule without going to the network,
+    /// falling back to the bundled copy.
+    pub fn load_offline(config: &AppConfig) -> Self {
+        std::fs::read_to_string(cache_path(config))
+            .ok()
+            .and_then(|body| serde_json::from_str(&body).ok())
+            .unwrap_or_else(Self::bundled)
+    }
+
+    fn bundled() -> Self {
+        serde_json::from_str(BUNDLED_SCHEDULE).expect("The bundled release schedule is invalid")
+    }
+
+    pub fn release_line(&self, version: &Version) -> Option<&ReleaseLine> {
+        let key = match version {
+            Version::Semver(v) if v.major == 0 => format!("v0.{}", v.minor),
+            Version::Semver(v) => format!("v{}", v.major),
+            _ => return None,
+        };
+        self.0.get(&key)
+    }
+
+    pub fn status(&self, version: &Version, config: &AppConfig) -> Option<EolStatus> {
+        let end = self.release_line(version)?.end;
+        let today = chrono::Utc::now().date_naive();
+        let window = chrono::Duration::days(i64::from(config.eol_warning_days()));
+
+        Some(if end <= today {
+            EolStatus::EndOfLife { end }
+        } else if end - today <= window {
+            EolStatus::EndingSoon { end }
+        } else {
+            EolStatus::Supported { end }
+        })
+    }
+
+    /// Prints a warning when the version is past, or close to, its end-of-life.
+    pub fn warn_if_eol(&self, version: &Version, config: &AppConfig) {
+        match self.status(version, config) {
+            Some(EolStatus::EndOfLife { end }) => outln!(
+                config,
+                Error,
+                "{} Node {} reached its end-of-life on {} and no longer receives security updates",
+                "warning:".yellow().bold(),
+                version.v_str().cyan(),
+                end
+            ),
+            Some(EolStatus::EndingSoon { end }) => outln!(
+                config,
+                Error,
+                "{} Node {} reaches its end-of-life on {}",
+                "warning:".yellow().bold(),
+                version.v_str().cyan(),
+                end
+            ),
+            Some(EolStatus::Supported { .. }) | None => {}
+        }
+    }
+}
+
+fn cache_path(config: &AppConfig) -> std::path::PathBuf {
+    config.cache_dir().join("release-schedule.json")
+}
+
+/// Touched when fetching the schedule fails, to wait before trying again
+fn attempt_path(config: &AppConfig) -> std::path::PathBuf {
+    config.cache_dir().join("release-schedule.failed")
+}
+
+fn modified_within(path: &std::path::Path, duration: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < duration)
+}
+
+fn fetch(config: &AppConfig) -> Result<String, crate::http::Error> {
+    let response = crate::http::get(config.release_schedule_url.as_str())?;
+    response.error_for_status()?.text()
+}
@@ -0,0 +1,105 @@
+//! The last version `enm switch --caps-lock-when-needed` resolved from a directory
+//! for a shim, so the shell hook running it on every `cd` returns right away
+//! when nothing the resolution depends on changed.
+
+use crate::app_config::AppConfig;
+use crate::sift_method::SiftMethod;
+use crate::version_files::PATH_PARTS;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What the resolution of a directory depends on. Version files are compared
+/// by modification time, along with the directories of installations and aliases,
+/// which change when a version or an alias is added or removed.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Key {
+    dir: PathBuf,
+    sift_method: String,
+    cpu_arch: String,
+    version_files: Vec<(PathBuf, Option<SystemTime>)>,
+    installations_modified: Option<SystemTime>,
+    aliases_modified: Option<SystemTime>,
+    default_alias: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Entry {
+    key: Key,
+    installation: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct ResolutionCache {
+    path: PathBuf,
+    key: Key,
+}
+
+impl ResolutionCache {
+    /// The cache of resolving `dir` for the shim `plural_ctx`
+    pub fn new(plural_ctx: &Path, dir: &Path, config: &AppConfig) -> Self {
+        let dirs: Vec<&Path> = match config.sift_method() {
+            SiftMethod::Local => vec![dir],
+            SiftMethod::Recursive => dir.ancestors().collect(),
+        };
+        let version_files = dirs
+            .into_iter()
+            .flat_map(|dir| PATH_PARTS.iter().map(move |part| dir.join(part)))
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        Self {
+            path: path_for(plural_ctx),
+            key: Key {
+                dir: dir.to_path_buf(),
+                sift_method: config.sift_method().as_str().to_string(),
+                cpu_arch: config.cpu_arch.to_string(),
+                version_files,
+                installations_modified: modified(&config.installations_dir()),
+                aliases_modified: modified(&config.aliases_dir()),
+                default_alias: std::fs::read_link(config.version_std_dir()).ok(),
+            },
+        }
+    }
+
+    /// Whether the resolution is unchanged, and the shim still points at it
+    pub fn is_fresh(&self, plural_ctx: &Path) -> bool {
+        let Some(entry) = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|body| serde_json::from_str::<Entry>(&body).ok())
+        else {
+            return false;
+        };
+        entry.key == self.key
+            && std::fs::read_link(plural_ctx).is_ok_and(|target| target == entry.installation)
+    }
+
+    /// Remembers that the directory resolved to `installation`
+    pub fn save(self, installation: &Path) {
+        let entry = Entry {
+            key: self.key,
+            installation: installation.to_path_buf(),
+        };
+        let body = serde_json::to_string(&entry).expect("Can't serialize the resolution");
+        if let Err(err) = std::fs::write(&self.path, body) {
+            debug!("Unable to write {}: {}", self.path.display(), err);
+        }
+    }
+}
+
+/// The cache file of a shim, which is removed along with it
+pub fn path_for(plural_ctx: &Path) -> PathBuf {
+    let mut file_name = plural_ctx.file_name().unwrap_or_default().to_owned();
+    file_name.push(".resolution");
+    plural_ctx.with_file_name(file_name)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
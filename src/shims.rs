@@ -0,0 +1,163 @@
+//! Every `enm source` creates a shim, a symlink named `<pid>_<millis>_sh` in
+//! `plural_ctx_storage()`, which `enm switch` points at a version. The pid is
+//! the one of the shell evaluating `enm source`, so once that shell exits,
+//! the shim can be removed.
+//!
+//! Shims named `<pid>_<millis>` were created by an older enm, with the pid of
+//! `enm source` itself, which exits right away. Whether their shell still runs
+//! is unknown, so they are only removed once they weren't switched for a day.
+
+use crate::app_config::AppConfig;
+use crate::symlinked::remove_symlink_dir;
+use log::debug;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use sysinfo::{Pid, ProcessRefreshKind, System};
+
+/// Ends the name of the shims named after the shell which owns them
+pub const SHELL_MARKER: &str = "sh";
+
+/// How long a shim created by an older enm is kept after it was last switched
+const LEGACY_SHIM_MIN_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The process a shim belongs to, from its name
+#[derive(Debug, PartialEq, Eq)]
+enum Owner {
+    /// A process which uses the shim for as long as it runs
+    Process(u32),
+    /// The `enm source` which created the shim, before shims were named after their shell.
+    /// It exited right away, so its pid tells nothing about the shell.
+    Legacy,
+}
+
+/// The pid of the process running `enm source`, which owns the shim it creates
+pub fn owner_pid() -> u32 {
+    #[cfg(unix)]
+    {
+        std::os::unix::process::parent_id()
+    }
+
+    #[cfg(windows)]
+    {
+        let mut system = System::new();
+        sysinfo::get_current_pid()
+            .ok()
+            .filter(|pid| system.refresh_process_specifics(*pid, ProcessRefreshKind::new()))
+            .and_then(|pid| system.process(pid)?.parent())
+            .map_or_else(std::process::id, Pid::as_u32)
+    }
+}
+
+/// Removes the shims of shells which exited, along with the leftovers of
+/// interrupted switches. Shims created by an older enm are removed once they
+/// weren't switched for a day. Returns the removed shims.
+pub fn remove_stale(config: &AppConfig) -> std::io::Result<Vec<PathBuf>> {
+    let storage = config.plural_ctx_storage();
+    let entries = match std::fs::read_dir(&storage) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        entries => entries?,
+    };
+
+    let mut system = System::new();
+    let mut removed = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let Some(owner) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(owner_of)
+        else {
+            continue;
+        };
+        let in_use = match owner {
+            Owner::Process(pid) => {
+                system.refresh_process_specifics(Pid::from_u32(pid), ProcessRefreshKind::new())
+            }
+            Owner::Legacy => modified_within(&path, LEGACY_SHIM_MIN_AGE),
+        };
+        if in_use || config.plural_ctx() == Some(path.as_path()) {
+            continue;
+        }
+
+        debug!("Removing the stale shim {}", path.display());
+        match remove_symlink_dir(&path) {
+            Ok(()) => {
+                std::fs::remove_file(crate::resolution_cache::path_for(&path)).ok();
+                removed.push(path);
+            }
+            Err(err) => debug!("Unable to remove {}: {}", path.display(), err),
+        }
+    }
+    Ok(removed)
+}
+
+/// The owner in the name of a shim, or of the temporary link
+/// `.<shim>.<pid>.tmp` an `enm switch` replaces it with
+fn owner_of(file_name: &str) -> Option<Owner> {
+    if let Some(temp_name) = file_name.strip_prefix('.') {
+        let pid = temp_name.strip_suffix(".tmp")?.rsplit('.').next()?;
+        return pid.parse().ok().map(Owner::Process);
+    }
+
+    let (pid, rest) = file_name.split_once('_')?;
+    let pid = pid.parse().ok()?;
+    match rest.split_once('_') {
+        Some((millis, SHELL_MARKER)) => {
+            millis.parse::<i64>().ok()?;
+            Some(Owner::Process(pid))
+        }
+        Some(_) => None,
+        None => {
+            rest.parse::<i64>().ok()?;
+            Some(Owner::Legacy)
+        }
+    }
+}
+
+fn modified_within(path: &Path, duration: Duration) -> bool {
+    std::fs::symlink_metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .map_or(true, |age| age < duration)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_of_shim() {
+        assert_eq!(owner_of("4242_1700000000000_sh"), Some(Owner::Process(4242)));
+    }
+
+    #[test]
+    fn test_owner_of_legacy_shim() {
+        assert_eq!(owner_of("4242_1700000000000"), Some(Owner::Legacy));
+    }
+
+    #[test]
+    fn test_owner_of_temporary_link() {
+        assert_eq!(
+            owner_of(".4242_1700000000000_sh.4300.tmp"),
+            Some(Owner::Process(4300))
+        );
+        assert_eq!(
+            owner_of(".4242_1700000000000.4300.tmp"),
+            Some(Owner::Process(4300))
+        );
+    }
+
+    #[test]
+    fn test_owner_of_other_files() {
+        assert_eq!(owner_of("4242_1700000000000_sh.json"), None);
+        assert_eq!(owner_of("4242_1700000000000_bash"), None);
+        assert_eq!(owner_of("4242_now_sh"), None);
+        assert_eq!(owner_of("shell_1700000000000"), None);
+        assert_eq!(owner_of(".4242_1700000000000_sh.tmp"), None);
+        assert_eq!(owner_of(".hidden"), None);
+        assert_eq!(owner_of("4242"), None);
+    }
+}
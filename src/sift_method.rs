@@ -3,10 +3,10 @@ use std::fmt::Display;
 
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum SiftMethod {
-    /// Look for NodeJS version in the current directory
+    /// Look for `NodeJS` version in the current directory
     #[default]
     Local,
-    /// Look for NodeJS version in the current directory. If not found, moving up to parent folders
+    /// Look for `NodeJS` version in the current directory. If not found, moving up to parent folders
     Recursive,
 }
 
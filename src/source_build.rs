@@ -0,0 +1,280 @@
+use crate::app_config::AppConfig;
+use crate::cpu_arch::CPUArch;
+use crate::loaders::ResponseProgress;
+use crate::outln;
+use crate::pressed::{Archive, Error as ExtractError};
+use crate::prtl_folder::DirectoryPortal;
+use crate::version::Version;
+use colored::Colorize;
+use indicatif::ProgressDrawTarget;
+use log::debug;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use thiserror::Error;
+use url::Url;
+
+/// Options for building Node from its source code, instead of downloading a binary.
+#[derive(clap::Args, Debug, Default, Clone)]
+pub struct SourceBuildOptions {
+    /// Build `NodeJS` from its source code instead of downloading a binary
+    #[clap(long)]
+    pub from_source: bool,
+
+    /// Build from a local source tree instead of downloading the source tarball.
+    /// Its `src/node_version.h` must match the requested version
+    #[clap(long, value_name = "DIR", requires = "from_source")]
+    pub source_dir: Option<PathBuf>,
+
+    /// A flag passed to `./configure`, like `--debug`. Can be repeated
+    #[clap(
+        long = "configure-flag",
+        value_name = "FLAG",
+        allow_hyphen_values = true,
+        requires = "from_source"
+    )]
+    pub configure_flags: Vec<String>,
+
+    /// The number of parallel `make` jobs. Defaults to the number of CPUs
+    #[clap(long, value_name = "N", requires = "from_source")]
+    pub jobs: Option<usize>,
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    HttpError {
+        #[from]
+        source: crate::http::Error,
+    },
+    #[error(transparent)]
+    IoError {
+        #[from]
+        source: std::io::Error,
+    },
+    #[error("Unable to extract the source tarball: {}", source)]
+    CantExtractFile {
+        #[from]
+        source: ExtractError,
+    },
+    #[error("The source tarball for {} was not found upstream", version)]
+    SourceNotFound { version: Version },
+    #[error("The fetched source tarball seems empty")]
+    EmptyTape,
+    #[error("The source directory {} doesn't contain a `configure` script", path.display())]
+    NotASourceTree { path: PathBuf },
+    #[error("`{}` failed with {}. The build log is kept at {}", step, status, log_path.display())]
+    BuildStepFailed {
+        step: String,
+        status: std::process::ExitStatus,
+        log_path: PathBuf,
+    },
+    #[error("The source directory {} is for Node {}, not {}", path.display(), found, requested)]
+    SourceVersionMismatch {
+        path: PathBuf,
+        found: String,
+        requested: String,
+    },
+    #[error("Building from source is only supported on Unix systems")]
+    UnsupportedPlatform,
+    #[error("Version already installed at {:?}", path)]
+    VersionAlreadyInstalled { path: PathBuf },
+}
+
+fn source_url(base_url: &Url, version: &Version) -> Url {
+    Url::parse(&format!(
+        "{}/{}/node-{}.tar.gz",
+        base_url.as_str().trim_end_matches('/'),
+        version,
+        version
+    ))
+    .unwrap()
+}
+
+/// Downloads the source tarball of a version and extracts it into `into`,
+/// returning the path of the extracted source tree.
+fn download_source(
+    version: &Version,
+    node_dist_mirror: &Url,
+    into: &Path,
+    show_loaders: bool,
+) -> Result<PathBuf, Error> {
+    let url = source_url(node_dist_mirror, version);
+    debug!("Going to call for {}", crate::http::redact(&url));
+    let response = crate::http::get(url.as_str())?;
+
+    if !response.status().is_success() {
+        return Err(Error::SourceNotFound {
+            version: version.clone(),
+        });
+    }
+
+    let extract = Archive::source();
+    if show_loaders {
+        extract.extract_pressed_into(
+            into,
+            ResponseProgress::new(response, ProgressDrawTarget::stderr()),
+        )?;
+    } else {
+        extract.extract_pressed_into(into, response)?;
+    }
+
+    let source_tree = std::fs::read_dir(into)?.next().ok_or(Error::EmptyTape)??;
+    Ok(source_tree.path())
+}
+
+/// Reads the version of a source tree from the defines in `src/node_version.h`,
+/// like `v20.11.1`
+fn source_tree_version(source_tree: &Path) -> Option<String> {
+    let header = std::fs::read_to_string(source_tree.join("src").join("node_version.h")).ok()?;
+    let define = |name: &str| {
+        header.lines().find_map(|line| {
+            let mut words = line.split_whitespace();
+            (words.next()? == "#define" && words.next()? == name)
+                .then(|| words.next())
+                .flatten()
+        })
+    };
+    Some(format!(
+        "v{}.{}.{}",
+        define("NODE_MAJOR_VERSION")?,
+        define("NODE_MINOR_VERSION")?,
+        define("NODE_PATCH_VERSION")?
+    ))
+}
+
+/// Runs a build step in `source_tree`, appending its output to the build log.
+fn run_step(
+    source_tree: &Path,
+    program: &str,
+    args: &[String],
+    log_file: &std::fs::File,
+    log_path: &Path,
+) -> Result<(), Error> {
+    debug!("Running {} {:?} in {:?}", program, args, source_tree);
+    let status = Command::new(program)
+        .args(args)
+        .current_dir(source_tree)
+        .stdin(Stdio::null())
+        .stdout(log_file.try_clone()?)
+        .stderr(log_file.try_clone()?)
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::BuildStepFailed {
+            step: std::iter::once(program)
+                .chain(args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" "),
+            status,
+            log_path: log_path.to_path_buf(),
+        })
+    }
+}
+
+/// Build a Node version from source and install it like a downloaded one.
+/// `cpu_arch` is the architecture of this machine, which the build is for.
+pub fn build_from_source(
+    version: &Version,
+    node_dist_mirror: &Url,
+    options: &SourceBuildOptions,
+    cpu_arch: CPUArch,
+    config: &AppConfig,
+    show_loaders: bool,
+) -> Result<(), Error> {
+    if cfg!(windows) {
+        return Err(Error::UnsupportedPlatform);
+    }
+
+    let installations_dir = config.installations_dir();
+    crate::arch_installs::migrate(config, version)?;
+    let installation_dir = crate::arch_installs::arch_dir(config, version, cpu_arch);
+
+    if installation_dir.exists() {
+        return Err(Error::VersionAlreadyInstalled {
+            path: installation_dir,
+        });
+    }
+
+    let temp_installations_dir = installations_dir.join(".downloads");
+    std::fs::create_dir_all(&temp_installations_dir)?;
+    let portal = DirectoryPortal::new_in(&temp_installations_dir, installation_dir.clone());
+
+    let downloaded_source = tempfile::TempDir::new_in(&temp_installations_dir)?;
+    let source_tree = if let Some(source_dir) = &options.source_dir {
+        source_dir.clone()
+    } else {
+        outln!(config, Info, "Downloading the source code of {}", version.v_str().cyan());
+        download_source(version, node_dist_mirror, downloaded_source.path(), show_loaders)?
+    };
+
+    if !source_tree.join("configure").exists() {
+        return Err(Error::NotASourceTree { path: source_tree });
+    }
+    if options.source_dir.is_some() {
+        let found = source_tree_version(&source_tree).ok_or_else(|| Error::NotASourceTree {
+            path: source_tree.clone(),
+        })?;
+        if found != version.v_str() {
+            return Err(Error::SourceVersionMismatch {
+                path: source_tree,
+                found,
+                requested: version.v_str(),
+            });
+        }
+    }
+
+    let logs_dir = config.base_dir_with_default().join("build-logs");
+    std::fs::create_dir_all(&logs_dir)?;
+    let log_path = logs_dir.join(format!(
+        "node-{}-{}.log",
+        version,
+        chrono::Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    let log_file = std::fs::File::create(&log_path)?;
+
+    let jobs = options.jobs.unwrap_or_else(|| {
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    });
+    // The prefix is baked into the binary, so it is the final one, and
+    // `make install` stages the installation under `DESTDIR` instead
+    let prefix = installation_dir.join("installation");
+    let staging_dir = tempfile::TempDir::new_in(&temp_installations_dir)?;
+    let configure_args: Vec<String> = std::iter::once(format!("--prefix={}", prefix.display()))
+        .chain(options.configure_flags.iter().cloned())
+        .collect();
+
+    outln!(
+        config,
+        Info,
+        "Building {} with {} jobs. This can take a while, follow the log at {}",
+        version.v_str().cyan(),
+        jobs,
+        log_path.display()
+    );
+    run_step(&source_tree, "./configure", &configure_args, &log_file, &log_path)?;
+    run_step(&source_tree, "make", &[format!("-j{jobs}")], &log_file, &log_path)?;
+    run_step(
+        &source_tree,
+        "make",
+        &[
+            String::from("install"),
+            format!("DESTDIR={}", staging_dir.path().display()),
+        ],
+        &log_file,
+        &log_path,
+    )?;
+    let staged_prefix = staging_dir
+        .path()
+        .join(prefix.strip_prefix("/").unwrap_or(&prefix));
+    std::fs::rename(staged_prefix, portal.join("installation"))?;
+
+    std::fs::create_dir_all(installations_dir.join(version.v_str()))?;
+    portal.teleport()?;
+    crate::arch_installs::set_primary_if_missing(config, version, cpu_arch)?;
+    drop(log_file);
+    std::fs::remove_file(&log_path).ok();
+
+    Ok(())
+}
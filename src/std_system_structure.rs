@@ -25,7 +25,7 @@ fn cache_dir(basedirs: &impl BaseStrategy) -> PathBuf {
     xdg_dir("XDG_CACHE_HOME").unwrap_or_else(|| basedirs.cache_dir())
 }
 
-/// A helper struct for std_system_structure in enm that uses XDG Base Directory Specification
+/// A helper struct for `std_system_structure` in enm that uses XDG Base Directory Specification
 /// if applicable for the platform.
 #[derive(Debug)]
 pub struct StdStructure(
@@ -75,4 +75,12 @@ impl StdStructure {
             .unwrap_or_else(|| cache_dir(basedirs));
         dir.join("enm_shim")
     }
+
+    pub fn home_dir(&self) -> PathBuf {
+        self.strategy().home_dir().to_path_buf()
+    }
+
+    pub fn cache_dir(&self) -> PathBuf {
+        cache_dir(self.strategy()).join("enm")
+    }
 }
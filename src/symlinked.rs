@@ -24,6 +24,39 @@ pub fn remove_symlink_dir<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Points the symlink `to` at `from`, creating it if it is missing.
+///
+/// On Unix, the new symlink is created next to `to` and renamed over it,
+/// so anything following `to` meanwhile sees either the old or the new target.
+#[cfg(unix)]
+pub fn replace_symlink_dir<P: AsRef<Path>, U: AsRef<Path>>(from: P, to: U) -> std::io::Result<()> {
+    let to = to.as_ref();
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(to.file_name().unwrap_or_default());
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = to.with_file_name(temp_name);
+
+    // A leftover of a process which had the same pid
+    std::fs::remove_file(&temp_path).ok();
+    symlink_dir(from, &temp_path)?;
+    std::fs::rename(&temp_path, to).inspect_err(|_| {
+        std::fs::remove_file(&temp_path).ok();
+    })
+}
+
+/// Points the symlink `to` at `from`, creating it if it is missing.
+///
+/// Junctions can't be renamed over each other, so `to` is deleted first.
+/// If the creation fails, the deletion error is returned first, if any.
+#[cfg(windows)]
+pub fn replace_symlink_dir<P: AsRef<Path>, U: AsRef<Path>>(from: P, to: U) -> std::io::Result<()> {
+    let symlink_deletion_result = remove_symlink_dir(&to);
+    match symlink_dir(from, &to) {
+        ok @ Ok(()) => ok,
+        err @ Err(_) => symlink_deletion_result.and(err),
+    }
+}
+
 pub fn shallow_read_symlink<P: AsRef<Path>>(path: P) -> std::io::Result<std::path::PathBuf> {
     std::fs::read_link(path)
 }
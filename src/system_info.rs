@@ -32,10 +32,87 @@ pub fn platform_cpu_arch() -> &'static str {
     "arm64"
 }
 
+#[cfg(target_arch = "riscv64")]
+pub fn platform_cpu_arch() -> &'static str {
+    "riscv64"
+}
+
+#[cfg(target_arch = "loongarch64")]
+pub fn platform_cpu_arch() -> &'static str {
+    "loong64"
+}
+
 #[cfg(all(
     target_pointer_width = "64",
-    not(any(target_arch = "arm", target_arch = "aarch64"))
+    not(any(
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+        target_arch = "loongarch64"
+    ))
 ))]
 pub fn platform_cpu_arch() -> &'static str {
     "x64"
 }
+
+/// Whether the system libc is musl, like on Alpine.
+/// `ldd` is a script naming the libc it belongs to, which is cheaper to read
+/// than spawning it. Without `ldd`, the musl dynamic loader gives it away.
+#[cfg(target_os = "linux")]
+pub fn is_musl() -> bool {
+    match std::fs::read("/usr/bin/ldd") {
+        Ok(ldd) => ldd.windows(4).any(|window| window == b"musl"),
+        Err(_) => std::fs::read_dir("/lib").is_ok_and(|entries| {
+            entries.filter_map(Result::ok).any(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with("ld-musl-"))
+            })
+        }),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn is_musl() -> bool {
+    false
+}
+
+/// An operating system Node publishes builds for, named like in the archive names
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Platform {
+    Linux,
+    #[value(alias = "macos", alias = "osx")]
+    Darwin,
+    #[value(alias = "windows")]
+    Win,
+}
+
+impl Platform {
+    #[cfg(unix)]
+    pub fn current() -> Self {
+        match platform_name() {
+            "darwin" => Self::Darwin,
+            _ => Self::Linux,
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn current() -> Self {
+        Self::Win
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Linux => "linux",
+            Self::Darwin => "darwin",
+            Self::Win => "win",
+        }
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -46,6 +46,47 @@ pub fn conjectr_shell() -> Option<Box<dyn Shell>> {
 }
 
 fn get_process_info(pid: u32) -> Result<ProcessInfo, ProcessInfoError> {
+    #[cfg(target_os = "linux")]
+    match proc_process_info(pid) {
+        Ok(process_info) => return Ok(process_info),
+        Err(err) => debug!("Falling back to ps for pid {}: {}", pid, err),
+    }
+
+    ps_process_info(pid)
+}
+
+/// Reads the process info from procfs, which is much faster than spawning `ps`
+/// and works in containers which don't have it.
+#[cfg(target_os = "linux")]
+fn proc_process_info(pid: u32) -> Result<ProcessInfo, ProcessInfoError> {
+    let proc_dir = std::path::Path::new("/proc").join(pid.to_string());
+    let stat = std::fs::read_to_string(proc_dir.join("stat"))?;
+    let command = std::fs::read_to_string(proc_dir.join("comm"))?;
+
+    Ok(ProcessInfo {
+        parent_pid: parent_pid_from_stat(&stat)?,
+        command: command.trim_end().into(),
+    })
+}
+
+/// The parent pid in the contents of `/proc/<pid>/stat`, or `None` for
+/// processes without a parent.
+#[cfg(target_os = "linux")]
+fn parent_pid_from_stat(stat: &str) -> Result<Option<u32>, ProcessInfoError> {
+    // The command in `stat` is in parentheses and can contain spaces, so the
+    // fields are read after the last closing one: `pid (comm) state ppid ...`
+    let ppid = stat
+        .rsplit_once(')')
+        .and_then(|(_, fields)| fields.split_whitespace().nth(1))
+        .ok_or_else(|| ProcessInfoError::Parse {
+            expectation: "Unable to read the ppid from /proc, should be the fourth field of stat",
+            got: stat.to_string(),
+        })?;
+
+    Ok(ppid.parse().ok().filter(|ppid| *ppid != 0))
+}
+
+fn ps_process_info(pid: u32) -> Result<ProcessInfo, ProcessInfoError> {
     use std::io::{BufRead, BufReader};
     use std::process::Command;
 
@@ -99,3 +140,41 @@ enum ProcessInfoError {
         expectation: &'static str,
     },
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parent_pid_from_stat() {
+        let stat = "4242 (zsh) S 4200 4242 4242 34816 4242 4194560 2961";
+        assert_eq!(parent_pid_from_stat(stat).unwrap(), Some(4200));
+    }
+
+    #[test]
+    fn test_parent_pid_from_stat_with_parentheses_in_command() {
+        let stat = "4242 (my (weird) shell) S 4200 4242 4242 34816";
+        assert_eq!(parent_pid_from_stat(stat).unwrap(), Some(4200));
+    }
+
+    #[test]
+    fn test_parent_pid_from_stat_without_parent() {
+        let stat = "1 (init) S 0 1 1 0 -1 4194560";
+        assert_eq!(parent_pid_from_stat(stat).unwrap(), None);
+    }
+
+    #[test]
+    fn test_parent_pid_from_truncated_stat() {
+        assert!(parent_pid_from_stat("4242 (zsh").is_err());
+        assert!(parent_pid_from_stat("4242 (zsh) S").is_err());
+    }
+
+    #[test]
+    fn test_proc_process_info_of_current_process() {
+        let process_info = proc_process_info(std::process::id()).unwrap();
+        assert_eq!(
+            process_info.parent_pid,
+            Some(std::os::unix::process::parent_id())
+        );
+    }
+}
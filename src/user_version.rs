@@ -1,3 +1,4 @@
+use crate::channel::Channel;
 use crate::version::Version;
 use std::str::FromStr;
 
@@ -6,6 +7,8 @@ pub enum UserVersion {
     OnlyMajor(u64),
     MajorMinor(u64, u64),
     Full(Version),
+    /// A version in a release channel, like `rc/23` or `nightly/latest`
+    Channel(Channel, Box<UserVersion>),
 }
 
 impl UserVersion {
@@ -33,6 +36,7 @@ impl UserVersion {
     pub fn matches(&self, version: &Version, config: &crate::app_config::AppConfig) -> bool {
         match (self, version) {
             (Self::Full(a), b) if a == b => true,
+            (Self::Full(Version::Alias(name)), Version::Linked(linked)) if name == linked => true,
             (Self::Full(user_version), maybe_alias) => {
                 match (user_version.alias_name(), maybe_alias.find_aliases(config)) {
                     (None, _) | (_, Err(_)) => false,
@@ -41,14 +45,46 @@ impl UserVersion {
                     }
                 }
             }
-            (_, Version::Bypassed | Version::Lts(_) | Version::Alias(_) | Version::Latest) => false,
-            (Self::OnlyMajor(major), Version::Semver(other)) => *major == other.major,
-            (Self::MajorMinor(major, minor), Version::Semver(other)) => {
-                *major == other.major && *minor == other.minor
+            (
+                _,
+                Version::Bypassed
+                | Version::Lts(_)
+                | Version::Alias(_)
+                | Version::Latest
+                | Version::Linked(_),
+            ) => false,
+            (Self::Channel(channel, inner), Version::Semver(other)) => {
+                Channel::of_version(other) == *channel
+                    && match inner.as_ref() {
+                        Self::Full(Version::Latest) => true,
+                        Self::Full(Version::Semver(v)) => v == other,
+                        inner => inner.matches_numbers(other),
+                    }
+            }
+            // Partial versions only match release builds, so `20` never resolves to a nightly
+            (_, Version::Semver(other)) => {
+                Channel::of_version(other) == Channel::Release && self.matches_numbers(other)
             }
         }
     }
 
+    fn matches_numbers(&self, other: &node_semver::Version) -> bool {
+        match self {
+            Self::OnlyMajor(major) => *major == other.major,
+            Self::MajorMinor(major, minor) => *major == other.major && *minor == other.minor,
+            Self::Full(_) | Self::Channel(..) => false,
+        }
+    }
+
+    /// The release channel this version should be looked up in.
+    pub fn channel(&self) -> Channel {
+        match self {
+            Self::Channel(channel, _) => *channel,
+            Self::Full(Version::Semver(v)) => Channel::of_version(v),
+            _ => Channel::Release,
+        }
+    }
+
     /// The conjectrred alias for the user version, if it exists.
     pub fn conjectrred_alias(&self) -> Option<Version> {
         match self {
@@ -70,6 +106,7 @@ impl std::fmt::Display for UserVersion {
             Self::Full(x) => x.fmt(f),
             Self::OnlyMajor(major) => write!(f, "v{major}.x.x"),
             Self::MajorMinor(major, minor) => write!(f, "v{major}.{minor}.x"),
+            Self::Channel(channel, inner) => write!(f, "{channel}/{inner}"),
         }
     }
 }
@@ -81,6 +118,17 @@ fn skip_first_v(str: &str) -> &str {
 impl FromStr for UserVersion {
     type Err = node_semver::SemverError;
     fn from_str(s: &str) -> Result<UserVersion, Self::Err> {
+        if let Some((prefix, rest)) = s.trim().split_once('/') {
+            if let Ok(channel) = Channel::from_str(prefix) {
+                let inner = if rest.eq_ignore_ascii_case("latest") {
+                    Self::Full(Version::Latest)
+                } else {
+                    Self::from_str(rest)?
+                };
+                return Ok(Self::Channel(channel, Box::new(inner)));
+            }
+        }
+
         match Version::parse(s) {
             Ok(v) => Ok(Self::Full(v)),
             Err(e) => {
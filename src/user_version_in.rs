@@ -1,3 +1,4 @@
+use crate::arch_installs;
 use crate::available_versions;
 use crate::app_config::AppConfig;
 use crate::symlinked;
@@ -29,7 +30,7 @@ pub fn user_version_in<'a>(
     requested_version: &'a UserVersion,
     config: &'a AppConfig,
 ) -> Result<Option<ApplicableVersion>, Error> {
-    let all_versions = available_versions::list(config.installations_dir())
+    let all_versions = available_versions::list(config)
         .map_err(|source| Error::VersionListing { source })?;
 
     let result = if let UserVersion::Full(Version::Bypassed) = requested_version {
@@ -57,9 +58,16 @@ pub fn user_version_in<'a>(
         } else if alias_path.exists() {
             info!("Using Node for alias {}", alias_name.cyan());
             Some(ApplicableVersion {
-                path: alias_path,
+                path: arch_installs::alias_installation_for(config, &alias_path)?,
                 version: Version::Alias(alias_name),
             })
+        } else if crate::linked::is_linked(config, &alias_name) {
+            info!("Using linked Node {}", alias_name.cyan());
+            let version = Version::Linked(alias_name);
+            Some(ApplicableVersion {
+                path: version.installation_path(config),
+                version,
+            })
         } else {
             return Err(Error::CantFindVersion {
                 requested_version: requested_version.clone(),
@@ -67,18 +75,16 @@ pub fn user_version_in<'a>(
         }
     } else {
         let version_now = requested_version.to_version(&all_versions, config);
-        version_now.map(|version| {
-            info!("Using Node {}", version.to_string().cyan());
-            let path = config
-                .installations_dir()
-                .join(version.to_string())
-                .join("installation");
-
-            ApplicableVersion {
-                path,
-                version: version.clone(),
+        match version_now {
+            Some(version) => {
+                info!("Using Node {}", version.to_string().cyan());
+                Some(ApplicableVersion {
+                    path: arch_installs::installation_for(config, version)?,
+                    version: version.clone(),
+                })
             }
-        })
+            None => None,
+        }
     };
 
     Ok(result)
@@ -90,4 +96,9 @@ pub enum Error {
     CantFindVersion { requested_version: UserVersion },
     #[error("Unable to list local installed versions: {}", source)]
     VersionListing { source: available_versions::Error },
+    #[error(transparent)]
+    NotInstalledForArch {
+        #[from]
+        source: arch_installs::NotInstalled,
+    },
 }
@@ -11,6 +11,8 @@ pub enum Version {
     Alias(String),
     Latest,
     Bypassed,
+    /// A Node prefix outside of enm, registered with `enm link`
+    Linked(String),
 }
 
 fn first_letter_is_number(s: &str) -> bool {
@@ -62,7 +64,7 @@ impl Version {
             v @ (Self::Lts(_) | Self::Alias(_) | Self::Latest) => {
                 config.aliases_dir().join(v.alias_name().unwrap())
             }
-            v @ Self::Semver(_) => config
+            v @ (Self::Semver(_) | Self::Linked(_)) => config
                 .installations_dir()
                 .join(v.v_str())
                 .join("installation"),
@@ -70,6 +72,12 @@ impl Version {
     }
 
     pub fn root_path(&self, config: &app_config::AppConfig) -> Option<std::path::PathBuf> {
+        if let v @ (Self::Semver(_) | Self::Linked(_)) = self {
+            // Canonicalizing would lead into the architecture the `installation` link
+            // points to, or into the linked prefix, which enm doesn't own
+            let path = config.installations_dir().join(v.v_str());
+            return path.exists().then_some(path);
+        }
         let path = self.installation_path(config);
         let mut canon_path = path.canonicalize().ok()?;
         canon_path.pop();
@@ -95,6 +103,7 @@ impl std::fmt::Display for Version {
             Self::Semver(semver) => write!(f, "v{semver}"),
             Self::Alias(alias) => write!(f, "{alias}"),
             Self::Latest => write!(f, "latest"),
+            Self::Linked(name) => write!(f, "{name}"),
         }
     }
 }
@@ -109,7 +118,9 @@ impl FromStr for Version {
 impl PartialEq<node_semver::Version> for Version {
     fn eq(&self, other: &node_semver::Version) -> bool {
         match self {
-            Self::Bypassed | Self::Lts(_) | Self::Alias(_) | Self::Latest => false,
+            Self::Bypassed | Self::Lts(_) | Self::Alias(_) | Self::Latest | Self::Linked(_) => {
+                false
+            }
             Self::Semver(v) => v == other,
         }
     }
@@ -8,7 +8,7 @@ use std::io::Read;
 use std::path::Path;
 use std::str::FromStr;
 
-const PATH_PARTS: [&str; 3] = [".nvmrc", ".node-version", "package.json"];
+pub const PATH_PARTS: [&str; 3] = [".nvmrc", ".node-version", "package.json"];
 
 pub fn get_user_version_for_directory(
     path: impl AsRef<Path>,
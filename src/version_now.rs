@@ -1,6 +1,7 @@
 use thiserror::Error;
 
 use crate::app_config::AppConfig;
+use crate::cpu_arch::CPUArch;
 use crate::machine_semver;
 use crate::version::Version;
 
@@ -11,19 +12,35 @@ pub fn version_now(config: &AppConfig) -> Result<Option<Version>, Error> {
         return Ok(Some(Version::Bypassed));
     }
 
-    if let Ok(resolved_path) = std::fs::canonicalize(plural_ctx) {
-        let installation_path = resolved_path
+    if let Ok(resolved_path) = crate::linked::resolve_installation(plural_ctx) {
+        let mut installation_path = resolved_path
             .parent()
             .expect("plural_ctx path can't be in the root");
+        // Installations for another architecture are one level deeper
+        if installation_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.parse::<CPUArch>().is_ok())
+        {
+            installation_path = installation_path
+                .parent()
+                .expect("An architecture is in a version directory");
+        }
         let file_name = installation_path
             .file_name()
             .expect("Unable to get filename")
             .to_str()
             .expect("Invalid OS string");
-        let version = Version::parse(file_name).map_err(|source| Error::VersionError {
-            source,
-            version: file_name.to_string(),
-        })?;
+        let version = match Version::parse(file_name) {
+            Ok(Version::Alias(name)) => Version::Linked(name),
+            Ok(version) => version,
+            Err(source) => {
+                return Err(Error::VersionError {
+                    source,
+                    version: file_name.to_string(),
+                })
+            }
+        };
         Ok(Some(version))
     } else {
         Ok(None)
@@ -0,0 +1,102 @@
+use crate::alias::{list_aliases, StoredAlias};
+use crate::app_config::AppConfig;
+use crate::version::Version;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A single version entry, as printed by the `--json` flag of
+/// `show-local`, `show-remote` and `inuse`.
+///
+/// Every key is always present, so consumers can rely on the shape:
+///
+/// * `version`: the version string, like `v20.11.1` or `system`
+/// * `aliases`: names of the aliases pointing at this version
+/// * `lts`: the LTS codename in lowercase, or `null`
+/// * `is_current`: whether this version is active in the current shell
+/// * `is_default`: whether the `default` alias points at this version
+/// * `path`: the installation path, or `null` when it is not installed
+#[derive(Debug, Serialize)]
+pub struct VersionRecord {
+    pub version: String,
+    pub aliases: Vec<String>,
+    pub lts: Option<String>,
+    pub is_current: bool,
+    pub is_default: bool,
+    pub path: Option<PathBuf>,
+}
+
+/// The local state needed to describe versions: aliases and the version in use.
+pub struct LocalState {
+    aliases: HashMap<String, Vec<StoredAlias>>,
+    current: Option<Version>,
+}
+
+impl LocalState {
+    pub fn load(config: &AppConfig) -> std::io::Result<Self> {
+        Ok(Self {
+            aliases: generate_aliases_hash(config)?,
+            current: crate::version_now::version_now(config).ok().flatten(),
+        })
+    }
+
+    pub fn current(&self) -> Option<&Version> {
+        self.current.as_ref()
+    }
+
+    pub fn aliases_for(&self, version: &Version) -> &[StoredAlias] {
+        self.aliases
+            .get(&version.v_str())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Describes a version. `lts` should be the codename from the remote index if it is known.
+    /// Otherwise, it is inferred from `lts-*` aliases pointing at the version.
+    pub fn record(
+        &self,
+        version: &Version,
+        lts: Option<&str>,
+        installed: bool,
+        config: &AppConfig,
+    ) -> VersionRecord {
+        let aliases: Vec<String> = self
+            .aliases_for(version)
+            .iter()
+            .map(|alias| alias.name().to_string())
+            .collect();
+        let lts = lts.map(str::to_lowercase).or_else(|| {
+            aliases
+                .iter()
+                .filter_map(|alias| alias.strip_prefix("lts-"))
+                .find(|codename| *codename != "latest")
+                .map(String::from)
+        });
+        let path = match version {
+            Version::Semver(_) if installed => Some(version.installation_path(config)),
+            Version::Linked(name) => crate::linked::target(config, name).ok(),
+            _ => None,
+        };
+
+        VersionRecord {
+            version: version.v_str(),
+            is_default: aliases.iter().any(|alias| alias == "default"),
+            is_current: self.current.as_ref() == Some(version),
+            aliases,
+            lts,
+            path,
+        }
+    }
+}
+
+fn generate_aliases_hash(config: &AppConfig) -> std::io::Result<HashMap<String, Vec<StoredAlias>>> {
+    let mut aliases = list_aliases(config)?;
+    let mut hashmap: HashMap<String, Vec<StoredAlias>> = HashMap::with_capacity(aliases.len());
+    for alias in aliases.drain(..) {
+        if let Some(value) = hashmap.get_mut(alias.s_ver()) {
+            value.push(alias);
+        } else {
+            hashmap.insert(alias.s_ver().into(), vec![alias]);
+        }
+    }
+    Ok(hashmap)
+}
@@ -3,7 +3,8 @@ use crate::version::Version;
 use std::str::FromStr;
 
 pub fn find_version_std(config: &AppConfig) -> Option<Version> {
-    if let Ok(version_path) = config.version_std_dir().canonicalize() {
+    // Canonicalizing would lead into the architecture directory of the version
+    if let Ok(version_path) = crate::linked::resolve_installation(&config.version_std_dir()) {
         let file_name = version_path.parent()?.file_name()?;
         Version::from_str(file_name.to_str()?).ok()?.into()
     } else {